@@ -1,55 +1,184 @@
+use std::borrow::Cow;
+use std::env;
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::Duration;
+
+use clap::ValueEnum;
+use terminal_size::Width;
+use unicode_width::UnicodeWidthStr;
+
+/// The escape sequence resetting all colors.
+const RESET: &str = "\x1b[0m";
+
+/// Set by `--no-color`, which takes precedence over everything else.
+static COLOR_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// The width of the terminal, detected on first use or set by `--width`.
+static TERMINAL_WIDTH: OnceLock<Option<usize>> = OnceLock::new();
+
+/// Turns colored output off for the rest of the run, as `--no-color` does.
+pub fn disable_color() {
+    COLOR_DISABLED.store(true, Ordering::Relaxed);
+}
+
+/// Returns whether the output should be colored.
+///
+/// The checks are, in order:
+/// 1. `--no-color` or a non-empty `NO_COLOR` turn colors off.
+/// 2. A `CLICOLOR_FORCE` other than `0` turns colors on, e.g. when piping the art into another program.
+/// 3. Otherwise colors are used when standard output is a terminal, unless `TERM` is `dumb`
+///    and `COLORTERM` does not advertise color support.
+///
+/// # Returns
+///
+/// `true` if ANSI color sequences should be written.
+pub fn color_enabled() -> bool {
+    let set = |name: &str| env::var_os(name).is_some_and(|v| !v.is_empty());
+
+    if COLOR_DISABLED.load(Ordering::Relaxed) || set("NO_COLOR") {
+        return false;
+    }
+    if env::var("CLICOLOR_FORCE").is_ok_and(|v| !v.is_empty() && v != "0") {
+        return true;
+    }
+    io::stdout().is_terminal()
+        && (set("COLORTERM") || env::var("TERM").map_or(true, |term| term != "dumb"))
+}
+
+/// Returns the text as is, or stripped of its escape sequences when colors are disabled.
+fn without_colors(text: &str) -> Cow<'_, str> {
+    if color_enabled() {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(text.lines().map(strip_ansi).collect::<Vec<_>>().join("\n"))
+    }
+}
+
+/// Paints a background behind every line of the art.
+///
+/// Each line is padded to the width of the widest one so the background forms a
+/// rectangle, the background is restored after every reset inside the line, and
+/// the line ends with a reset so the background does not bleed past the art.
+///
+/// # Arguments
+///
+/// * `art` - The ASCII art to paint behind.
+/// * `background` - The ANSI escape sequence of the background, empty for none.
+///
+/// # Returns
+///
+/// The art with its background, or as is when `background` is empty or colors are disabled.
+fn with_background<'a>(art: &'a str, background: &str) -> Cow<'a, str> {
+    if background.is_empty() || !color_enabled() {
+        return Cow::Borrowed(art);
+    }
+
+    let width = art.lines().map(visible_width).max().unwrap_or(0);
+    let restored = format!("{RESET}{background}");
+    let lines: Vec<String> = art
+        .lines()
+        .map(|line| {
+            format!(
+                "{background}{}{}{RESET}",
+                line.replace(RESET, &restored),
+                " ".repeat(width - visible_width(line))
+            )
+        })
+        .collect();
+    Cow::Owned(lines.join("\n"))
+}
+
 /// Draws an ASCII art representation of a Pokémon, aligning the description next to the art.
 ///
 /// The function displays the provided ASCII art and aligns the given description text
 /// next to the art. The description starts from a calculated index based on the
 /// midpoint of the ASCII art. If there are no descriptions, a message indicating
-/// the lack of descriptions for the specified language is displayed.
+/// the lack of descriptions is displayed.
 ///
 /// # Arguments
 ///
+/// * `out` - The writer the art is rendered to.
 /// * `art` - A string slice that holds the ASCII art to be displayed.
-/// * `desc_lines` - A vector of string slices containing the description lines to be shown next to the art.
-/// * `padding_left` - The number of spaces to pad to the left of each line of art.
-/// * `language` - The language of the description; used in the message when no descriptions are available.
-pub fn draw_pokemon_art(art: &str, desc_lines: Vec<&str>, padding_left: usize, language: &str) {
-    let lines: Vec<&str> = art.lines().collect();
-    let desc_width = desc_lines.iter().map(|line| line.len()).max().unwrap_or(0);
-
-    // Calculate the midpoint of the ASCII art
-    let mid_index = lines.len() / 2;
-
-    // Determine the starting index for descriptions
-    let start_index = if lines.len() >= 3 {
-        mid_index.saturating_sub(1) // Start one line above the midpoint if there are 3 or more lines
+/// * `options` - The description lines to be shown next to the art, the padding, the
+///   message shown when no descriptions are available unless `quiet` is set, and the
+///   description and background colors.
+///
+/// Colors are left out when [`color_enabled`] is false.
+pub fn draw_pokemon_art(out: &mut dyn Write, art: &str, options: &RenderOptions) -> io::Result<()> {
+    let RenderOptions {
+        desc_lines,
+        padding_left,
+        padding_top,
+        missing_notice,
+        quiet,
+        desc_color,
+        background,
+        ..
+    } = *options;
+    let art = without_colors(art);
+    let art = &with_background(&art, background);
+    let (desc_color, reset) = if color_enabled() {
+        (desc_color, RESET)
     } else {
-        mid_index // Use midpoint for less than 3 lines
+        ("", "")
     };
+    print_padding_top(out, padding_top)?;
+    let lines: Vec<&str> = art.lines().collect();
+    let desc_width = desc_lines
+        .iter()
+        .map(|line| visible_width(line))
+        .max()
+        .unwrap_or(0);
+
+    let start_index = desc_start_index(lines.len());
 
     // Print the art with descriptions starting from the determined start index
     for (i, line) in lines.iter().enumerate() {
-        print!("{: <1$}", line, padding_left);
-        print!("\t\t");
+        let mut row = format!("{: <1$}\t\t", line, padding_left);
 
         // Print the description if within the range and adjust its starting position
         if i >= start_index && i - start_index < desc_lines.len() {
             // Calculate the padding for the description to start at the determined index
             let description_padding = padding_left + desc_width + 1; // Add extra space for visual separation
-            println!(
-                "\x1b[37m{: <1$}\x1b[0m",
-                desc_lines[i - start_index],
-                description_padding
-            );
-        } else {
-            println!();
+                                                                     // Padded by display width, as CJK characters take two columns
+            let desc = desc_lines[i - start_index];
+            let padding = description_padding.saturating_sub(visible_width(desc));
+            row.push_str(&format!(
+                "{}{}{}{}",
+                desc_color,
+                desc,
+                " ".repeat(padding),
+                reset
+            ));
         }
+        writeln!(out, "{}", trim_line_end(&row))?;
     }
 
-    // Inform if there are no descriptions available
-    if desc_lines.is_empty() {
-        println!(
-            "{: <1$}No descriptions available for language: {} {}",
-            "", padding_left, language
-        );
+    // Inform if there are no descriptions available, unless asked not to
+    if desc_lines.is_empty() && !quiet {
+        writeln!(out, "{: <1$}{2}", "", padding_left, missing_notice)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the index of the art line the description starts beside.
+///
+/// # Arguments
+///
+/// * `art_lines` - The number of lines of the art.
+fn desc_start_index(art_lines: usize) -> usize {
+    // Calculate the midpoint of the ASCII art
+    let mid_index = art_lines / 2;
+
+    // Determine the starting index for descriptions
+    if art_lines >= 3 {
+        mid_index.saturating_sub(1) // Start one line above the midpoint if there are 3 or more lines
+    } else {
+        mid_index // Use midpoint for less than 3 lines
     }
 }
 
@@ -57,42 +186,71 @@ pub fn draw_pokemon_art(art: &str, desc_lines: Vec<&str>, padding_left: usize, l
 ///
 /// The function displays the provided ASCII art and shows the given description
 /// text below it. If no descriptions are available, a message indicating the lack
-/// of descriptions for the specified language is displayed.
+/// of descriptions is displayed.
 ///
 /// # Arguments
 ///
+/// * `out` - The writer the art is rendered to.
 /// * `art` - A string slice that holds the ASCII art to be displayed.
-/// * `desc_lines` - A vector of string slices containing the description lines to be shown below the art.
-/// * `padding_left` - The number of spaces to pad to the left of each line of art.
-/// * `language` - The language of the description; used in the message when no descriptions are available.
+/// * `options` - The description lines to be shown below the art, the padding, the
+///   message shown when no descriptions are available unless `quiet` is set, and the
+///   description and background colors.
+///
+/// Colors are left out when [`color_enabled`] is false.
 pub fn draw_pokemon_art_under(
+    out: &mut dyn Write,
     art: &str,
-    desc_lines: Vec<&str>,
-    padding_left: usize,
-    language: &str,
-) {
+    options: &RenderOptions,
+) -> io::Result<()> {
+    let RenderOptions {
+        desc_lines,
+        padding_left,
+        padding_top,
+        missing_notice,
+        quiet,
+        desc_color,
+        background,
+        ..
+    } = *options;
+    let art = without_colors(art);
+    let art = &with_background(&art, background);
+    let (desc_color, reset) = if color_enabled() {
+        (desc_color, RESET)
+    } else {
+        ("", "")
+    };
+    print_padding_top(out, padding_top)?;
     let lines: Vec<&str> = art.lines().collect();
-    let desc_width = desc_lines.iter().map(|line| line.len()).max().unwrap_or(0);
 
     // Print the ASCII art
     for line in lines.iter() {
-        print!("{: <1$}", line, padding_left);
-        println!(); // New line after each art line
+        let row = format!("{: <1$}", line, padding_left);
+        writeln!(out, "{}", trim_line_end(&row))?;
     }
 
-    // Print descriptions if available
+    // Print descriptions if available, wrapped to the width of the art, but not narrower
+    // than Pokédex entries are written nor wider than the terminal
     if !desc_lines.is_empty() {
-        let description_padding = padding_left + desc_width + 1; // Add extra space for visual separation
+        let art_width = lines
+            .iter()
+            .map(|line| visible_width(line))
+            .max()
+            .unwrap_or(0);
+        let mut wrap_width = art_width.max(DESC_LINE_WIDTH);
+        if let Some(columns) = terminal_width() {
+            wrap_width = wrap_width.min(columns);
+        }
         for desc in desc_lines {
-            println!("\x1b[37m{: <1$}\x1b[0m", desc, description_padding);
+            for part in wrap_words(desc.trim_end(), wrap_width) {
+                writeln!(out, "{desc_color}{part}{reset}")?;
+            }
         }
-    } else {
-        // Inform if there are no descriptions available
-        println!(
-            "{: <1$}No descriptions available for language: {} {}",
-            "", padding_left, language
-        );
+    } else if !quiet {
+        // Inform if there are no descriptions available, unless asked not to
+        writeln!(out, "{: <1$}{2}", "", padding_left, missing_notice)?;
     }
+
+    Ok(())
 }
 
 /// Prints an ASCII art representation of a Pokémon with optional left padding.
@@ -102,11 +260,1059 @@ pub fn draw_pokemon_art_under(
 ///
 /// # Arguments
 ///
+/// * `out` - The writer the art is rendered to.
 /// * `art` - A string slice that holds the ASCII art to be displayed.
 /// * `padding_left` - The number of spaces to pad to the left of each line of art.
-pub fn print_ascii_art(art: &str, padding_left: usize) {
+/// * `padding_top` - The number of blank lines to print before the art.
+/// * `background` - The ANSI escape sequence of the background behind the art, empty for none.
+///
+/// Colors are left out when [`color_enabled`] is false.
+pub fn print_ascii_art(
+    out: &mut dyn Write,
+    art: &str,
+    padding_left: usize,
+    padding_top: usize,
+    background: &str,
+) -> io::Result<()> {
+    let art = without_colors(art);
+    let art = &with_background(&art, background);
+    print_padding_top(out, padding_top)?;
+    for line in art.lines() {
+        let row = format!("{: <1$}", line, padding_left);
+        writeln!(out, "{}", trim_line_end(&row))?;
+    }
+
+    Ok(())
+}
+
+/// Removes the whitespace at the end of a rendered line, so padding does not show as
+/// blocks on some terminals nor bloat the files written with `--output-file`.
+///
+/// The escape sequences among the trailing whitespace (e.g. a final reset) are kept,
+/// and so are the spaces painted with a background color, which are part of the art.
+fn trim_line_end(line: &str) -> String {
+    let mut trimmed = String::with_capacity(line.len());
+    // The whitespace and escape sequences not yet followed by anything visible
+    let (mut pending, mut pending_escapes) = (String::new(), String::new());
+    let mut background = false;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Read the escape sequence up to and including its final letter
+            let mut sequence = String::from(c);
+            for c in chars.by_ref() {
+                sequence.push(c);
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+            // Backgrounds are 40-47, 100-107 and 48 (256-color and RGB)
+            let params = sequence.trim_start_matches("\x1b[").trim_end_matches('m');
+            match params.split(';').next().unwrap_or_default() {
+                "" | "0" | "49" => background = false,
+                "48" => background = true,
+                code => {
+                    if let Ok(40..=47 | 100..=107) = code.parse::<u8>() {
+                        background = true;
+                    }
+                }
+            }
+            pending.push_str(&sequence);
+            pending_escapes.push_str(&sequence);
+        } else if c.is_whitespace() && !background {
+            pending.push(c);
+        } else {
+            trimmed.push_str(&pending);
+            trimmed.push(c);
+            pending.clear();
+            pending_escapes.clear();
+        }
+    }
+    trimmed.push_str(&pending_escapes);
+    trimmed
+}
+
+/// Represents where the description goes relative to the art.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// Only the art, without description.
+    ArtOnly,
+    /// The description next to the art, see [`draw_pokemon_art`].
+    Beside,
+    /// The description below the art, see [`draw_pokemon_art_under`].
+    Under,
+}
+
+/// Represents the options used to render a Pokémon's art and description.
+pub struct RenderOptions<'a> {
+    pub layout: Layout,
+    pub desc_lines: &'a [&'a str],
+    pub padding_left: usize,
+    pub padding_top: usize,
+    pub missing_notice: &'a str,
+    pub quiet: bool,
+    pub desc_color: &'a str,
+    pub background: &'a str,
+}
+
+/// Renders the art and its description, writing every line as soon as it is laid out.
+///
+/// Nothing is accumulated, so writing to a line-buffered standard output streams
+/// long galleries line by line.
+///
+/// # Arguments
+///
+/// * `out` - The writer the art is rendered to.
+/// * `art` - A string slice that holds the ASCII art to be displayed.
+/// * `options` - The layout, description and padding to render with.
+pub fn render_to<W: Write>(out: &mut W, art: &str, options: &RenderOptions) -> io::Result<()> {
+    match options.layout {
+        Layout::ArtOnly => print_ascii_art(
+            out,
+            art,
+            options.padding_left,
+            options.padding_top,
+            options.background,
+        ),
+        Layout::Beside => draw_pokemon_art(out, art, options),
+        Layout::Under => draw_pokemon_art_under(out, art, options),
+    }
+}
+
+/// Renders the art and its description to a string, for callers needing the whole
+/// output at once (e.g. to draw a frame around it).
+///
+/// # Arguments
+///
+/// * `art` - A string slice that holds the ASCII art to be displayed.
+/// * `options` - The layout, description and padding to render with.
+///
+/// # Returns
+///
+/// The rendered output, as [`render_to`] would write it.
+pub fn render(art: &str, options: &RenderOptions) -> String {
+    let mut buffer = Vec::new();
+    // Writing to a vector never fails
+    let _ = render_to(&mut buffer, art, options);
+    String::from_utf8_lossy(&buffer).into_owned()
+}
+
+/// Prints already rendered art line by line, pausing between lines to animate its reveal.
+///
+/// The art is printed at once when standard output is not a terminal, so piping is not slowed down.
+///
+/// # Arguments
+///
+/// * `out` - The writer the art is printed to, flushed after every line.
+/// * `rendered` - The rendered art, e.g. from [`render`].
+/// * `delay` - The pause between two lines.
+pub fn reveal_line_by_line(out: &mut dyn Write, rendered: &str, delay: Duration) -> io::Result<()> {
+    if !io::stdout().is_terminal() {
+        return write!(out, "{rendered}");
+    }
+
+    for line in rendered.lines() {
+        writeln!(out, "{line}")?;
+        out.flush()?;
+        thread::sleep(delay);
+    }
+    Ok(())
+}
+
+/// Cycles through already rendered frames in place, until the process is interrupted.
+///
+/// Each frame is drawn over the previous one by moving the cursor back up and clearing
+/// what was below it, so frames of different heights leave nothing behind.
+///
+/// # Arguments
+///
+/// * `out` - The writer the frames are printed to, flushed after every frame.
+/// * `frames` - The rendered frames, e.g. from [`render`], in the order they are shown.
+/// * `delay` - The time each frame stays on screen.
+pub fn animate(out: &mut dyn Write, frames: &[String], delay: Duration) -> io::Result<()> {
+    let mut previous_height = 0;
+    for frame in frames.iter().cycle() {
+        if previous_height > 0 {
+            write!(out, "\x1b[{previous_height}A\x1b[J")?;
+        }
+        write!(out, "{frame}")?;
+        out.flush()?;
+        previous_height = frame.lines().count();
+        thread::sleep(delay);
+    }
+    Ok(())
+}
+
+/// The width Pokédex entries are written in, which descriptions under the art are never
+/// wrapped below.
+const DESC_LINE_WIDTH: usize = 50;
+
+/// Splits a line of text into lines of at most `width` columns, breaking between words.
+///
+/// Words wider than `width` are kept whole on a line of their own.
+fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.width() + 1 + word.width() > width {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Prints the given number of blank lines.
+fn print_padding_top(out: &mut dyn Write, padding_top: usize) -> io::Result<()> {
+    for _ in 0..padding_top {
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// Wraps text in an OSC 8 hyperlink, which supporting terminals make clickable
+/// and other terminals ignore.
+///
+/// # Arguments
+///
+/// * `text` - The text shown.
+/// * `url` - The address the text links to.
+///
+/// # Returns
+///
+/// The text between the opening and closing escape sequences of the link.
+pub fn hyperlink(text: &str, url: &str) -> String {
+    format!("\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\")
+}
+
+/// Sets the width used for the layout for the rest of the run, as `--width` does.
+///
+/// It has no effect once the width has been detected or set.
+pub fn set_terminal_width(columns: usize) {
+    let _ = TERMINAL_WIDTH.set(Some(columns));
+}
+
+/// Returns the width of the terminal standard output is attached to.
+///
+/// The width is detected once and reused for the rest of the run, so every Pokémon
+/// of a batch is laid out the same way even if the terminal is resized meanwhile.
+///
+/// # Returns
+///
+/// The number of columns, or `None` when the output is not a terminal (e.g. piped)
+/// and no width was set with [`set_terminal_width`].
+pub fn terminal_width() -> Option<usize> {
+    *TERMINAL_WIDTH.get_or_init(|| terminal_size::terminal_size().map(|(Width(w), _)| w as usize))
+}
+
+/// Checks whether the description fits beside the art within the given width.
+///
+/// Every row is built the way `draw_pokemon_art` lays it out, then measured once
+/// its trailing whitespace is trimmed, ignoring ANSI escape sequences.
+///
+/// # Arguments
+///
+/// * `art` - A string slice that holds the ASCII art to be displayed.
+/// * `desc_lines` - The description lines to be shown next to the art.
+/// * `padding_left` - The number of spaces to pad to the left of each line of art.
+/// * `columns` - The number of columns available.
+pub fn fits_beside(art: &str, desc_lines: &[&str], padding_left: usize, columns: usize) -> bool {
+    let start_index = desc_start_index(art.lines().count());
+
+    art.lines().enumerate().all(|(i, line)| {
+        let desc = i
+            .checked_sub(start_index)
+            .and_then(|i| desc_lines.get(i))
+            .unwrap_or(&"");
+        let row = format!("{: <1$}\t\t{desc}", line, padding_left);
+        visible_width(&expand_tabs(&trim_line_end(&row))) <= columns
+    })
+}
+
+/// Returns whether the terminal is known to lack 24-bit color support.
+///
+/// Truecolor is assumed unless `COLORTERM` does not advertise it and the
+/// terminal is one known to only handle 256 colors.
+pub fn limited_color_terminal() -> bool {
+    let colorterm = env::var("COLORTERM").unwrap_or_default();
+    if colorterm == "truecolor" || colorterm == "24bit" {
+        return false;
+    }
+    env::var("TERM_PROGRAM").is_ok_and(|program| program == "Apple_Terminal")
+}
+
+/// Replaces the 24-bit colors of ASCII art with the nearest xterm 256-color palette entries.
+///
+/// Both foreground (`38;2;r;g;b`) and background (`48;2;r;g;b`) colors are
+/// converted, any other escape sequence is kept as is.
+///
+/// # Arguments
+///
+/// * `art` - A string slice that holds the ASCII art to be converted.
+///
+/// # Returns
+///
+/// The art using 256-color escape sequences.
+pub fn downsample_to_256(art: &str) -> String {
+    let mut downsampled = String::with_capacity(art.len());
+    let mut rest = art;
+
+    while let Some(start) = rest.find("\x1b[") {
+        downsampled.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let Some(end) = rest.find('m') else {
+            break;
+        };
+        let params: Vec<&str> = rest[2..end].split(';').collect();
+        match params.as_slice() {
+            [layer @ ("38" | "48"), "2", r, g, b] => {
+                match (r.parse::<u8>(), g.parse::<u8>(), b.parse::<u8>()) {
+                    (Ok(r), Ok(g), Ok(b)) => {
+                        downsampled.push_str(&format!("\x1b[{};5;{}m", layer, nearest_256(r, g, b)))
+                    }
+                    _ => downsampled.push_str(&rest[..=end]),
+                }
+            }
+            _ => downsampled.push_str(&rest[..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    downsampled.push_str(rest);
+    downsampled
+}
+
+/// Replaces every color of ASCII art with a single dark shade, leaving only its silhouette.
+///
+/// Both 24-bit and 256-color foreground and background colors are replaced,
+/// any other escape sequence is kept as is.
+///
+/// # Arguments
+///
+/// * `art` - A string slice that holds the ASCII art to be darkened.
+///
+/// # Returns
+///
+/// The art drawn in a single shade.
+pub fn silhouette_art(art: &str) -> String {
+    const SHADE: &str = "2;48;48;48";
+
+    let mut silhouette = String::with_capacity(art.len());
+    let mut rest = art;
+
+    while let Some(start) = rest.find("\x1b[") {
+        silhouette.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let Some(end) = rest.find('m') else {
+            break;
+        };
+        let params: Vec<&str> = rest[2..end].split(';').collect();
+        match params.as_slice() {
+            [layer @ ("38" | "48"), "2", _, _, _] | [layer @ ("38" | "48"), "5", _] => {
+                silhouette.push_str(&format!("\x1b[{};{}m", layer, SHADE))
+            }
+            _ => silhouette.push_str(&rest[..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    silhouette.push_str(rest);
+    silhouette
+}
+
+/// Represents the ways art can be recolored.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Palette {
+    Grayscale,
+    Sepia,
+    Invert,
+}
+
+impl Palette {
+    /// Maps an RGB color to its counterpart in this palette.
+    ///
+    /// Grayscale uses the Rec. 601 luminance weights, sepia the usual sepia tone matrix.
+    fn remap(self, (r, g, b): (u8, u8, u8)) -> (u8, u8, u8) {
+        let (r, g, b) = (r as f64, g as f64, b as f64);
+        let clamp = |v: f64| v.round().min(255.0) as u8;
+        match self {
+            Palette::Grayscale => {
+                let luminance = clamp(0.299 * r + 0.587 * g + 0.114 * b);
+                (luminance, luminance, luminance)
+            }
+            Palette::Sepia => (
+                clamp(0.393 * r + 0.769 * g + 0.189 * b),
+                clamp(0.349 * r + 0.686 * g + 0.168 * b),
+                clamp(0.272 * r + 0.534 * g + 0.131 * b),
+            ),
+            Palette::Invert => (255 - r as u8, 255 - g as u8, 255 - b as u8),
+        }
+    }
+}
+
+/// Recolors ASCII art with a palette.
+///
+/// Both 24-bit and 256-color foreground and background colors are remapped and
+/// written back as 24-bit colors, any other escape sequence is kept as is.
+///
+/// # Arguments
+///
+/// * `art` - A string slice that holds the ASCII art to be recolored.
+/// * `palette` - The palette to remap the colors with.
+///
+/// # Returns
+///
+/// The recolored art.
+pub fn recolor_art(art: &str, palette: Palette) -> String {
+    let mut recolored = String::with_capacity(art.len());
+    let mut rest = art;
+
+    while let Some(start) = rest.find("\x1b[") {
+        recolored.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let Some(end) = rest.find('m') else {
+            break;
+        };
+        let params: Vec<&str> = rest[2..end].split(';').collect();
+        let color = match params.as_slice() {
+            [layer @ ("38" | "48"), "2", r, g, b] => {
+                match (r.parse::<u8>(), g.parse::<u8>(), b.parse::<u8>()) {
+                    (Ok(r), Ok(g), Ok(b)) => Some((layer, (r, g, b))),
+                    _ => None,
+                }
+            }
+            [layer @ ("38" | "48"), "5", index] => {
+                index.parse::<u8>().ok().map(|i| (layer, rgb_of_256(i)))
+            }
+            _ => None,
+        };
+        match color {
+            Some((layer, rgb)) => {
+                let (r, g, b) = palette.remap(rgb);
+                recolored.push_str(&format!("\x1b[{};2;{};{};{}m", layer, r, g, b))
+            }
+            None => recolored.push_str(&rest[..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    recolored.push_str(rest);
+    recolored
+}
+
+/// Redraws ASCII art with plain ASCII characters, for terminals or fonts without the block glyphs.
+///
+/// Every painted cell becomes `#`, `+`, `:` or `.`, from its darkest to its lightest
+/// luminance, so the outlines stay dense. The colors are dropped, the art being
+/// monochrome once its glyphs no longer mix two colors.
+///
+/// # Arguments
+///
+/// * `art` - A string slice that holds the ASCII art to be converted.
+///
+/// # Returns
+///
+/// The art drawn with ASCII characters only.
+pub fn ascii_only_art(art: &str) -> String {
+    const RAMP: [char; 4] = ['#', '+', ':', '.'];
+
+    let mut converted = String::with_capacity(art.len() / 8);
     for line in art.lines() {
-        print!("{: <1$}", line, padding_left);
-        println!(); // New line after each art line
+        for cell in parse_cells(line) {
+            let fg = cell.fg.as_deref().and_then(rgb_of_sequence);
+            let bg = cell.bg.as_deref().and_then(rgb_of_sequence);
+            // The colors of the painted parts of the cell, none for a blank one
+            let painted = match cell.c {
+                '▀' | '▄' => [fg, bg],
+                ' ' => [bg, None],
+                c if c.is_ascii() => {
+                    converted.push(c);
+                    continue;
+                }
+                _ => [fg, None],
+            };
+            let luminances: Vec<u32> = painted
+                .iter()
+                .flatten()
+                .map(|&rgb| u32::from(Palette::Grayscale.remap(rgb).0))
+                .collect();
+            if luminances.is_empty() {
+                converted.push(' ');
+                continue;
+            }
+            let luminance = luminances.iter().sum::<u32>() / luminances.len() as u32;
+            converted.push(RAMP[luminance as usize * RAMP.len() / 256]);
+        }
+        converted.push('\n');
+    }
+    converted
+}
+
+/// Returns the RGB color set by a 24-bit or 256-color SGR sequence, e.g. `\x1b[38;2;0;0;0m`.
+fn rgb_of_sequence(sequence: &str) -> Option<(u8, u8, u8)> {
+    let params = sequence.strip_prefix("\x1b[")?.strip_suffix('m')?;
+    let params: Vec<&str> = params.split(';').collect();
+    match params.as_slice() {
+        ["38" | "48", "2", r, g, b] => Some((r.parse().ok()?, g.parse().ok()?, b.parse().ok()?)),
+        ["38" | "48", "5", index] => index.parse().ok().map(rgb_of_256),
+        _ => None,
+    }
+}
+
+/// Returns the RGB color of an entry of the xterm 256-color palette.
+///
+/// The first 16 colors depend on the terminal theme, the xterm defaults are used for them.
+fn rgb_of_256(index: u8) -> (u8, u8, u8) {
+    const SYSTEM: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (205, 0, 0),
+        (0, 205, 0),
+        (205, 205, 0),
+        (0, 0, 238),
+        (205, 0, 205),
+        (0, 205, 205),
+        (229, 229, 229),
+        (127, 127, 127),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (92, 92, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ];
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    match index {
+        0..=15 => SYSTEM[index as usize],
+        16..=231 => {
+            let i = (index - 16) as usize;
+            (LEVELS[i / 36], LEVELS[i / 6 % 6], LEVELS[i % 6])
+        }
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    }
+}
+
+/// Returns the index of the xterm 256-color palette entry closest to an RGB color.
+///
+/// Only the 6x6x6 color cube and the grayscale ramp are considered, as the
+/// first 16 colors depend on the terminal theme.
+fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    let nearest_level = |v: u8| {
+        (0..LEVELS.len())
+            .min_by_key(|&i| (LEVELS[i] as i32 - v as i32).abs())
+            .unwrap_or(0)
+    };
+    let distance = |(r1, g1, b1): (u8, u8, u8)| {
+        let (dr, dg, db) = (
+            r as i32 - r1 as i32,
+            g as i32 - g1 as i32,
+            b as i32 - b1 as i32,
+        );
+        dr * dr + dg * dg + db * db
+    };
+
+    let (ri, gi, bi) = (nearest_level(r), nearest_level(g), nearest_level(b));
+    let cube = (LEVELS[ri], LEVELS[gi], LEVELS[bi]);
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+
+    // The grayscale ramp goes from 8 to 238 in steps of 10
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_step = (average.saturating_sub(3) / 10).min(23) as u8;
+    let gray_value = 8 + gray_step * 10;
+    let gray_index = 232 + gray_step as usize;
+
+    if distance((gray_value, gray_value, gray_value)) < distance(cube) {
+        gray_index as u8
+    } else {
+        cube_index as u8
+    }
+}
+
+/// Flips ASCII art horizontally, keeping every character with its colors.
+///
+/// Each line is split into cells holding a character and the foreground and
+/// background colors active when it was printed, the cells are reversed and
+/// the line is printed again. Lines are padded to the widest one first so the
+/// art stays aligned once flipped.
+///
+/// # Arguments
+///
+/// * `art` - A string slice that holds the ASCII art to be flipped.
+///
+/// # Returns
+///
+/// The mirrored art.
+pub fn mirror_art(art: &str) -> String {
+    let lines: Vec<Vec<Cell>> = art.lines().map(parse_cells).collect();
+    let width = lines.iter().map(Vec::len).max().unwrap_or(0);
+
+    let mut mirrored = String::with_capacity(art.len());
+    for mut cells in lines {
+        cells.resize(width, Cell::default());
+        cells.reverse();
+
+        let (mut fg, mut bg) = (None, None);
+        for cell in &cells {
+            if cell.fg != fg || cell.bg != bg {
+                mirrored.push_str("\x1b[0m");
+                mirrored.push_str(cell.fg.as_deref().unwrap_or_default());
+                mirrored.push_str(cell.bg.as_deref().unwrap_or_default());
+                fg.clone_from(&cell.fg);
+                bg.clone_from(&cell.bg);
+            }
+            mirrored.push(match cell.c {
+                '▌' => '▐',
+                '▐' => '▌',
+                c => c,
+            });
+        }
+        mirrored.push_str("\x1b[0m\n");
+    }
+    mirrored
+}
+
+/// A character of ASCII art along with the colors it is printed with.
+#[derive(Clone)]
+struct Cell {
+    c: char,
+    fg: Option<String>,
+    bg: Option<String>,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            c: ' ',
+            fg: None,
+            bg: None,
+        }
+    }
+}
+
+/// Splits a line of ASCII art into cells, tracking the active SGR colors.
+fn parse_cells(line: &str) -> Vec<Cell> {
+    let mut cells = Vec::new();
+    let (mut fg, mut bg) = (None, None);
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            cells.push(Cell {
+                c,
+                fg: fg.clone(),
+                bg: bg.clone(),
+            });
+            continue;
+        }
+
+        // Read the escape sequence up to and including its final letter
+        let mut sequence = String::from(c);
+        for c in chars.by_ref() {
+            sequence.push(c);
+            if c.is_ascii_alphabetic() {
+                break;
+            }
+        }
+
+        let params = sequence.trim_start_matches("\x1b[").trim_end_matches('m');
+        match params {
+            "" | "0" => (fg, bg) = (None, None),
+            _ if params.starts_with("38") => fg = Some(sequence),
+            _ if params.starts_with("48") => bg = Some(sequence),
+            _ => {}
+        }
+    }
+    cells
+}
+
+/// Places two arts next to each other, row by row.
+///
+/// The shorter art is padded with blank lines above and below so both are
+/// vertically centered, and every line of the left art is padded to the width
+/// of the widest one, measured ignoring ANSI escape sequences.
+///
+/// # Arguments
+///
+/// * `left` - The art shown on the left.
+/// * `right` - The art shown on the right.
+/// * `gap` - The number of columns between the two arts.
+///
+/// # Returns
+///
+/// The combined art, ending with a newline. Colors are left out when
+/// [`color_enabled`] is false.
+pub fn side_by_side(left: &str, right: &str, gap: usize) -> String {
+    let left = without_colors(left);
+    let right = without_colors(right);
+    let height = left.lines().count().max(right.lines().count());
+    let centered = |art: &str| -> Vec<String> {
+        let top = (height - art.lines().count()) / 2;
+        let mut lines = vec![String::new(); top];
+        lines.extend(art.lines().map(str::to_string));
+        lines.resize(height, String::new());
+        lines
+    };
+    let (left, right) = (centered(&left), centered(&right));
+    let width = left
+        .iter()
+        .map(|line| visible_width(line))
+        .max()
+        .unwrap_or(0);
+
+    let reset = if color_enabled() { RESET } else { "" };
+    let mut combined = String::new();
+    for (left_line, right_line) in left.iter().zip(&right) {
+        let padding = width - visible_width(left_line) + gap;
+        combined.push_str(&format!(
+            "{left_line}{reset}{}{right_line}{reset}\n",
+            " ".repeat(padding)
+        ));
+    }
+    combined
+}
+
+/// Draws a Unicode box around already rendered content.
+///
+/// The width of every line is measured ignoring ANSI escape sequences and with
+/// tabs expanded, so colored lines do not push the right border out of place.
+///
+/// # Arguments
+///
+/// * `content` - The rendered lines to put inside the box.
+///
+/// # Returns
+///
+/// The framed content, ending with a newline.
+pub fn frame(content: &str) -> String {
+    let lines: Vec<String> = content.lines().map(expand_tabs).collect();
+    let width = lines.iter().map(|l| visible_width(l)).max().unwrap_or(0);
+
+    // Keeps the colors of a line from reaching the right border
+    let reset = if color_enabled() { "\x1b[0m" } else { "" };
+
+    let mut framed = format!("┌{}┐\n", "─".repeat(width + 2));
+    for line in &lines {
+        let padding = width - visible_width(line);
+        framed.push_str(&format!("│ {line}{reset}{} │\n", " ".repeat(padding)));
+    }
+    framed.push_str(&format!("└{}┘\n", "─".repeat(width + 2)));
+    framed
+}
+
+/// Returns the number of terminal columns a line occupies, ignoring ANSI escape sequences.
+///
+/// # Arguments
+///
+/// * `line` - The line to measure.
+pub fn visible_width(line: &str) -> usize {
+    strip_ansi(line).width()
+}
+
+/// Removes the ANSI escape sequences (e.g. colors) from a line.
+fn strip_ansi(line: &str) -> String {
+    let mut stripped = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip the sequence up to and including its final letter
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            stripped.push(c);
+        }
+    }
+    stripped
+}
+
+/// Replaces tabs with spaces up to the next tab stop, ignoring ANSI escape sequences.
+fn expand_tabs(line: &str) -> String {
+    const TAB_WIDTH: usize = 8;
+
+    let mut expanded = String::with_capacity(line.len());
+    let mut column = 0;
+    let mut in_escape = false;
+    for c in line.chars() {
+        match c {
+            '\x1b' => {
+                in_escape = true;
+                expanded.push(c);
+            }
+            _ if in_escape => {
+                in_escape = !c.is_ascii_alphabetic();
+                expanded.push(c);
+            }
+            '\t' => {
+                let spaces = TAB_WIDTH - column % TAB_WIDTH;
+                expanded.push_str(&" ".repeat(spaces));
+                column += spaces;
+            }
+            _ => {
+                expanded.push(c);
+                column += UnicodeWidthStr::width(c.encode_utf8(&mut [0; 4]) as &str);
+            }
+        }
+    }
+    expanded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fits_beside_measures_rows_as_drawn() {
+        // `ab` padded to 4 columns, tabs to column 16, then the description
+        let art = "ab\nab\nab";
+        assert!(fits_beside(art, &["1234"], 4, 20));
+        assert!(!fits_beside(art, &["1234"], 4, 19));
+        // Without a description the tabs are trimmed away
+        assert!(fits_beside(art, &[], 4, 4));
+    }
+
+    const RED: &str = "\x1b[38;2;255;0;0m";
+    const BLUE_BG: &str = "\x1b[48;2;0;0;255m";
+
+    /// Returns the characters of a line with their colors, for comparisons.
+    fn cells(line: &str) -> Vec<(char, Option<String>, Option<String>)> {
+        parse_cells(line)
+            .into_iter()
+            .map(|cell| (cell.c, cell.fg, cell.bg))
+            .collect()
+    }
+
+    #[test]
+    fn parse_cells_tracks_colors() {
+        let line = format!("{RED}A{BLUE_BG}B\x1b[0mC");
+
+        assert_eq!(
+            cells(&line),
+            [
+                ('A', Some(RED.to_string()), None),
+                ('B', Some(RED.to_string()), Some(BLUE_BG.to_string())),
+                ('C', None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn mirror_art_keeps_colors_with_their_cells() {
+        let art = format!("{RED}A\x1b[0m{BLUE_BG}B▌\x1b[0m\nC");
+
+        assert_eq!(
+            mirror_art(&art),
+            format!("\x1b[0m{BLUE_BG}▐B\x1b[0m{RED}A\x1b[0m\n  C\x1b[0m\n")
+        );
+    }
+
+    #[test]
+    fn mirror_art_round_trips() {
+        let art = format!("{RED}A\x1b[0m{BLUE_BG}B▌\x1b[0m\n{RED}CD");
+        let twice = mirror_art(&mirror_art(&art));
+
+        for (original, mirrored) in art.lines().zip(twice.lines()) {
+            let mut expected = cells(original);
+            expected.resize(3, (' ', None, None));
+            assert_eq!(cells(mirrored), expected);
+        }
+    }
+
+    #[test]
+    fn palette_remaps_red() {
+        assert_eq!(Palette::Grayscale.remap((255, 0, 0)), (76, 76, 76));
+        assert_eq!(Palette::Sepia.remap((255, 0, 0)), (100, 89, 69));
+        // Bright colors saturate instead of wrapping around
+        assert_eq!(Palette::Sepia.remap((255, 255, 255)), (255, 255, 239));
+        assert_eq!(Palette::Invert.remap((255, 0, 10)), (0, 255, 245));
+    }
+
+    #[test]
+    fn recolor_art_remaps_24_bit_and_256_colors() {
+        let art = format!("{RED}▀\x1b[48;5;196m▄\x1b[0m\x1b[1m \n");
+
+        assert_eq!(
+            recolor_art(&art, Palette::Grayscale),
+            "\x1b[38;2;76;76;76m▀\x1b[48;2;76;76;76m▄\x1b[0m\x1b[1m \n"
+        );
+    }
+
+    #[test]
+    fn recolor_art_keeps_other_sequences() {
+        let art = "\x1b[0m\x1b[1;4mA\x1b[38;2;300;0;0mB\x1b[31m";
+
+        assert_eq!(recolor_art(art, Palette::Invert), art);
+    }
+
+    /// Converts a single full block of the given gray to plain ASCII.
+    fn ascii_of_gray(gray: u8) -> String {
+        ascii_only_art(&format!("\x1b[38;2;{gray};{gray};{gray}m█\x1b[0m"))
+    }
+
+    #[test]
+    fn ascii_only_art_maps_luminance_to_the_ramp() {
+        assert_eq!(ascii_of_gray(0), "#\n");
+        assert_eq!(ascii_of_gray(63), "#\n");
+        assert_eq!(ascii_of_gray(64), "+\n");
+        assert_eq!(ascii_of_gray(128), ":\n");
+        assert_eq!(ascii_of_gray(192), ".\n");
+        assert_eq!(ascii_of_gray(255), ".\n");
+    }
+
+    #[test]
+    fn ascii_only_art_averages_half_blocks() {
+        // White over black averages to 127
+        let art = "\x1b[38;2;255;255;255m\x1b[48;2;0;0;0m▀\x1b[0m\x1b[38;5;16m▄";
+        assert_eq!(ascii_only_art(art), "+#\n");
+    }
+
+    #[test]
+    fn ascii_only_art_keeps_blanks_and_ascii() {
+        let art = format!("  x\n{BLUE_BG} \x1b[0m ");
+        assert_eq!(ascii_only_art(&art), "  x\n# \n");
+    }
+
+    #[test]
+    fn trim_line_end_removes_trailing_spaces() {
+        assert_eq!(trim_line_end("  ab   "), "  ab");
+        assert_eq!(trim_line_end("ab\t\t"), "ab");
+        assert_eq!(trim_line_end("   "), "");
+    }
+
+    #[test]
+    fn trim_line_end_keeps_escapes_around_trailing_spaces() {
+        assert_eq!(trim_line_end("ab  \x1b[0m  "), "ab\x1b[0m");
+        assert_eq!(
+            trim_line_end(&format!("{RED}a b  \x1b[0m\x1b[39m ")),
+            format!("{RED}a b\x1b[0m\x1b[39m")
+        );
+    }
+
+    #[test]
+    fn trim_line_end_keeps_spaces_painted_with_a_background() {
+        assert_eq!(trim_line_end("a\x1b[44m  \x1b[0m  "), "a\x1b[44m  \x1b[0m");
+        assert_eq!(
+            trim_line_end(&format!("a{BLUE_BG} \x1b[49m ")),
+            format!("a{BLUE_BG} \x1b[49m")
+        );
+    }
+
+    #[test]
+    fn rendered_lines_have_no_trailing_spaces() {
+        disable_color();
+        let options = RenderOptions {
+            layout: Layout::Beside,
+            desc_lines: &["A short entry."],
+            padding_left: 8,
+            padding_top: 0,
+            missing_notice: "",
+            quiet: true,
+            desc_color: "",
+            background: "",
+        };
+
+        for layout in [Layout::Beside, Layout::Under, Layout::ArtOnly] {
+            let options = RenderOptions { layout, ..options };
+            let rendered = render("ab \nabc\n a", &options);
+            assert!(rendered.lines().all(|line| line.trim_end() == line));
+        }
+    }
+
+    #[test]
+    fn wrap_words_breaks_between_words() {
+        let entry = "When several of these Pokémon gather, their electricity could build and cause lightning storms.";
+        let lines = wrap_words(entry, DESC_LINE_WIDTH);
+
+        assert_eq!(
+            lines,
+            [
+                "When several of these Pokémon gather, their",
+                "electricity could build and cause lightning",
+                "storms.",
+            ]
+        );
+        assert!(lines.iter().all(|line| line.width() <= DESC_LINE_WIDTH));
+        assert_eq!(lines.join(" "), entry);
+    }
+
+    #[test]
+    fn wrap_words_keeps_long_words_whole() {
+        assert_eq!(
+            wrap_words("a Fletchinder-Talonflame b", 5),
+            ["a", "Fletchinder-Talonflame", "b"]
+        );
+        assert_eq!(wrap_words("", 5), [""]);
+    }
+
+    #[test]
+    fn wrap_words_measures_display_width() {
+        // Each character takes two columns, so only two words of two fit in 9 columns
+        assert_eq!(
+            wrap_words("ピカ チュ ウは 電気", 9),
+            ["ピカ チュ", "ウは 電気"]
+        );
+        assert_eq!(wrap_words("Évoli évolue", 12), ["Évoli évolue"]);
+    }
+
+    #[test]
+    fn descriptions_beside_the_art_align_by_display_width() {
+        disable_color();
+        let options = RenderOptions {
+            layout: Layout::Beside,
+            desc_lines: &["ピカチュウ", "abc"],
+            padding_left: 2,
+            padding_top: 0,
+            missing_notice: "",
+            quiet: true,
+            desc_color: "",
+            background: "",
+        };
+        // Every description starts after the art and the tabs, with no padding left over
+        let rendered = render("a\nb\nc", &options);
+        assert_eq!(rendered, "a \t\tピカチュウ\nb \t\tabc\nc\n");
+
+        // Framed, the rows line up once the double-width description is measured
+        let framed = frame(&rendered);
+        let widths: Vec<usize> = framed.lines().map(visible_width).collect();
+        assert!(widths.iter().all(|&width| width == widths[0]));
+        assert_eq!(widths[0], 30);
+    }
+
+    #[test]
+    fn visible_width_counts_double_width_characters() {
+        assert_eq!(visible_width("Pikachu"), 7);
+        assert_eq!(visible_width("ピカチュウ"), 10);
+        assert_eq!(visible_width(&format!("{RED}ピカチュウ\x1b[0m")), 10);
+    }
+
+    #[test]
+    fn frame_aligns_double_width_lines() {
+        disable_color();
+
+        assert_eq!(
+            frame("ピカ\nab"),
+            "┌──────┐\n│ ピカ │\n│ ab   │\n└──────┘\n"
+        );
+    }
+
+    #[test]
+    fn frame_without_colors_has_no_escapes() {
+        disable_color();
+
+        assert_eq!(frame("ab\na"), "┌────┐\n│ ab │\n│ a  │\n└────┘\n");
     }
 }
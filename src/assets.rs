@@ -0,0 +1,178 @@
+use rust_embed::RustEmbed;
+
+use std::borrow::Cow;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::Config;
+
+#[derive(RustEmbed)]
+#[folder = "assets/"]
+struct Asset;
+
+/// Gives access to the colorscripts and the Pokémon database.
+///
+/// Files found in the optional assets directory take precedence over the
+/// embedded ones, which are used for every file missing from that directory.
+pub struct Assets {
+    dir: Option<PathBuf>,
+}
+
+impl Assets {
+    /// Creates the assets for the `assets_dir` set in the configuration, if any.
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            dir: config.assets_dir.as_ref().map(PathBuf::from),
+        }
+    }
+
+    /// Returns the content of a file, e.g. `colorscripts/regular/pikachu`.
+    ///
+    /// # Parameters
+    /// - `path`: The path of the file relative to the assets directory.
+    ///
+    /// # Returns
+    /// - `Option<Cow<[u8]>>`: The content of the file, or `None` if it exists nowhere.
+    pub fn get(&self, path: &str) -> Option<Cow<'static, [u8]>> {
+        self.get_local(path)
+            .or_else(|| Asset::get(path).map(|file| file.data))
+    }
+
+    /// Returns the content of a file from the assets directory only, ignoring the embedded files.
+    ///
+    /// # Parameters
+    /// - `path`: The path of the file relative to the assets directory.
+    ///
+    /// # Returns
+    /// - `Option<Cow<[u8]>>`: The content of the file, or `None` if there is no assets
+    ///   directory or the file is not in it.
+    pub fn get_local(&self, path: &str) -> Option<Cow<'static, [u8]>> {
+        self.dir
+            .as_ref()
+            .and_then(|dir| fs::read(dir.join(path)).ok())
+            .map(Cow::Owned)
+    }
+
+    /// Returns the content of the Pokémon database, `pokemon.json.gz` or `pokemon.json`.
+    ///
+    /// Both names are looked for in the assets directory before the embedded files,
+    /// so a plain database there is not shadowed by the embedded compressed one.
+    ///
+    /// # Returns
+    /// - `Option<Cow<[u8]>>`: The content of the database, or `None` if it exists nowhere.
+    pub fn pokemon_db(&self) -> Option<Cow<'static, [u8]>> {
+        const NAMES: [&str; 2] = ["pokemon.json.gz", "pokemon.json"];
+
+        NAMES
+            .iter()
+            .find_map(|name| self.get_local(name))
+            .or_else(|| {
+                NAMES
+                    .iter()
+                    .find_map(|name| Asset::get(name).map(|file| file.data))
+            })
+    }
+
+    /// Returns the frames of a piece of art, e.g. `colorscripts/regular/pikachu`.
+    ///
+    /// Static art is a single file, while animated art is a directory of frames
+    /// named `0`, `1`, ... in the order they are shown.
+    ///
+    /// # Parameters
+    /// - `path`: The path of the art relative to the assets directory.
+    ///
+    /// # Returns
+    /// - `Vec<Cow<[u8]>>`: The frames in order, a single one for static art, or none
+    ///   if the art exists nowhere.
+    pub fn get_frames(&self, path: &str) -> Vec<Cow<'static, [u8]>> {
+        if let Some(data) = self.get(path) {
+            return vec![data];
+        }
+
+        let dir = format!("{path}/");
+        let mut frames: Vec<(u32, String)> = self
+            .list(&dir)
+            .into_iter()
+            .filter_map(|file| Some((file.strip_prefix(&dir)?.parse().ok()?, file)))
+            .collect();
+        frames.sort();
+        frames
+            .iter()
+            .filter_map(|(_, file)| self.get(file))
+            .collect()
+    }
+
+    /// Lists the files in a directory and its subdirectories, e.g. `colorscripts/shiny/`.
+    ///
+    /// # Parameters
+    /// - `dir`: The directory relative to the assets directory, with a trailing slash.
+    ///
+    /// # Returns
+    /// - `Vec<String>`: The paths of the files relative to the assets directory, without duplicates.
+    pub fn list(&self, dir: &str) -> Vec<String> {
+        let mut files = Vec::new();
+        if let Some(root) = &self.dir {
+            list_dir(root, dir, &mut files);
+        }
+        files.extend(
+            Asset::iter()
+                .filter(|path| path.starts_with(dir))
+                .map(|path| path.into_owned()),
+        );
+
+        files.sort();
+        files.dedup();
+        files
+    }
+}
+
+/// Collects the files under a directory of the assets directory, e.g. the frames
+/// of animated art along with the static art.
+///
+/// # Parameters
+/// - `root`: The assets directory.
+/// - `dir`: The directory relative to `root`, with a trailing slash.
+/// - `files`: The paths relative to `root` the files are added to.
+fn list_dir(root: &Path, dir: &str, files: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(root.join(dir)) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let file_name = entry.file_name();
+        let (Ok(file_type), Some(name)) = (entry.file_type(), file_name.to_str()) else {
+            continue;
+        };
+        if file_type.is_dir() {
+            list_dir(root, &format!("{dir}{name}/"), files);
+        } else if file_type.is_file() {
+            files.push(format!("{dir}{name}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pokemon_db_prefers_a_plain_file_in_the_assets_dir() {
+        let dir = std::env::temp_dir().join(format!("kingler-{}-assets", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("pokemon.json"), "[]").unwrap();
+
+        let assets = Assets {
+            dir: Some(dir.clone()),
+        };
+        assert_eq!(assets.pokemon_db().as_deref(), Some(&b"[]"[..]));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn pokemon_db_falls_back_to_the_embedded_file() {
+        let assets = Assets { dir: None };
+        let embedded = assets.pokemon_db().unwrap();
+
+        assert_eq!(embedded[..2], [0x1f, 0x8b]);
+    }
+}
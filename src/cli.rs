@@ -1,8 +1,16 @@
+use crate::ascii::Palette;
+use crate::config::TextColor;
+use crate::export::{Format, LogFormat};
+use crate::shiny_hunting::HuntMethod;
 use crate::Shell;
-use clap::{arg, Command};
-use clap::{Args, Parser, Subcommand};
+use chrono::NaiveDate;
+use clap::builder::{PossibleValue, StringValueParser, TypedValueParser};
+use clap::{arg, Arg, Command};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use clap_complete::{generate, Generator};
+use std::ffi::OsStr;
 use std::io;
+use std::path::PathBuf;
 
 /// Represents the command-line interface (CLI) for the Pokémon application.
 #[derive(Parser, Debug)]
@@ -10,6 +18,34 @@ use std::io;
 pub struct Cli {
     #[clap(subcommand)]
     pub command: Commands,
+
+    /// Seed for the random choices, to get reproducible output
+    #[clap(long, global = true)]
+    pub seed: Option<u64>,
+
+    /// Never print colors, even to a terminal
+    #[clap(long, global = true)]
+    pub no_color: bool,
+
+    /// Lay the output out for this many columns instead of the terminal's width
+    #[clap(long, global = true, value_parser = clap::value_parser!(u16).range(1..))]
+    pub width: Option<u16>,
+
+    /// Pokémon database (pokemon.json, optionally gzip-compressed) to use instead of the embedded one
+    #[clap(long, global = true, value_name = "PATH")]
+    pub db: Option<PathBuf>,
+}
+
+impl Cli {
+    /// Returns whether the command prints JSON, with `show-completion --json`
+    /// or `export --format json`, so errors are reported as JSON too.
+    pub fn json_output(&self) -> bool {
+        match &self.command {
+            Commands::ShowCompletion(completion) => completion.json,
+            Commands::Export(export) => matches!(export.format, Format::Json),
+            _ => false,
+        }
+    }
 }
 
 /// Represents a shell name for generating completions.
@@ -18,102 +54,656 @@ pub struct ShellName {
     pub shell: Shell,
 }
 
+/// The forms offered by shell completions for `--form`.
+const FORM_VALUES: [&str; 12] = [
+    "regular",
+    "mega",
+    "mega-x",
+    "mega-y",
+    "gmax",
+    "alola",
+    "hisui",
+    "galar",
+    "paldea",
+    "random",
+    "all",
+    "shiny-only",
+];
+
+/// Parses `--form`, accepting any form (e.g. `therian` or `alolan`) while
+/// advertising `FORM_VALUES` to shell completions.
+#[derive(Clone)]
+struct FormValueParser;
+
+impl TypedValueParser for FormValueParser {
+    type Value = String;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        StringValueParser::new().parse_ref(cmd, arg, value)
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(FORM_VALUES.iter().map(PossibleValue::new)))
+    }
+}
+
 /// Represents the options for selecting a Pokémon by name.
 ///
 /// # Fields
 /// - `name`: The name of the Pokémon to show.
 /// - `form`: An optional parameter for specifying an alternative form of the Pokémon.
 /// - `shiny`: A flag indicating whether to show the shiny version of the Pokémon.
+/// - `no_shiny`: A flag turning off the shiny version the config shows by default.
+/// - `shiny_fallback`: A flag indicating whether to show the regular art when there is no shiny art.
 /// - `info`: A flag indicating whether to print the Pokédex entry if it exists.
-/// - `game_info`: The specific game for which to retrieve the Pokédex entry.
+/// - `no_info`: A flag turning off the Pokédex entry the config prints by default.
+/// - `game_info`: The specific game for which to retrieve the Pokédex entry, possibly abbreviated.
+/// - `desc_index`: An optional position selecting the Pokédex entry deterministically.
+/// - `desc_max_lines`: An optional number of lines the Pokédex entry is truncated to.
 /// - `no_title`: A flag that, if set, will prevent displaying the Pokémon's name.
 /// - `under`: A flag indicating whether to display the description under the Pokémon art.
+/// - `no_under`: A flag turning off the description under the art the config sets by default.
 /// - `padding_left`: An integer specifying the amount of left padding for display.
+/// - `padding_top`: An integer specifying the number of blank lines printed before the art.
+/// - `frame`: A flag indicating whether to draw a box around the art and description.
+/// - `mirror`: A flag indicating whether to flip the art horizontally.
+/// - `color256`: A flag indicating whether to convert the art to the 256-color palette.
+/// - `delay`: An optional number of milliseconds to wait between lines, to animate the art.
+/// - `animate`: An optional number of milliseconds each frame of animated art is shown for.
+/// - `output_file`: An optional file the output is written to instead of standard output.
+/// - `palette`: An optional palette the art is recolored with (grayscale, sepia or invert).
+/// - `ascii`: A flag indicating whether to draw the art with ASCII characters instead of block glyphs.
 /// - `stats`: A flag indicating whether to show the Pokémon's stats.
+/// - `radar`: A flag indicating whether to show the Pokémon's stats as a radar chart.
+/// - `show_types`: A flag indicating whether to show the Pokémon's types in the header.
+/// - `show_dex`: A flag indicating whether to show the National Dex number in the header.
 /// - `unique`: A flag inficating wheter the pokemon is unique or not.
-#[derive(Debug, Args)]
+/// - `no_desc_color`: A flag indicating whether to print the Pokédex entry in the terminal's default color.
+/// - `no_desc_source`: A flag indicating whether to leave out the game the Pokédex entry comes from.
+/// - `quiet`: A flag indicating whether to leave out the notice shown when there is no Pokédex entry.
+/// - `bg`: An optional color painted behind the art.
+/// - `list_forms`: A flag indicating whether to list the available forms instead of showing the Pokémon.
+/// - `track`: A flag indicating whether to record the Pokémon in the Pokédex tracker once shown.
+#[derive(Clone, Debug, Args)]
 pub struct Name {
     /// Name of the Pokémon to show
     pub name: String,
 
     /// Show an alternative form of the Pokémon. Can be one of: mega, mega-x,
-    /// mega-y, gmax, alola, hisui, galar, paldea
-    #[clap(short, long, default_value = "regular")]
+    /// mega-y, gmax, alola, hisui, galar, paldea (alolan, galarian, hisuian,
+    /// paldean and gigantamax are accepted too), random for any form with art,
+    /// all to show every form with art, shiny-only to show every form with
+    /// shiny art as shiny, or a comma-separated list of forms
+    #[clap(
+        short,
+        long,
+        default_value = "regular",
+        value_parser = FormValueParser,
+        hide_possible_values = true
+    )]
     pub form: String,
 
     /// Show the shiny version of the Pokémon instead
-    #[clap(short, long)]
+    #[clap(short, long, overrides_with = "no_shiny")]
     pub shiny: bool,
 
+    /// Show the regular version, even if the config shows the shiny one by default
+    #[clap(long, overrides_with = "shiny")]
+    pub no_shiny: bool,
+
+    /// Show the regular art, with a note, when the Pokémon has no shiny art
+    #[clap(long)]
+    pub shiny_fallback: bool,
+
     /// Print Pokédex entry (if it exists)
-    #[clap(short, long)]
+    #[clap(short, long, overrides_with = "no_info")]
     pub info: bool,
 
-    /// Pokédex entry for a specific game
+    /// Do not print the Pokédex entry, even if the config prints it by default
+    #[clap(long, overrides_with = "info")]
+    pub no_info: bool,
+
+    /// Pokédex entry for a specific game, or the start or part of its name (e.g. omega)
     #[clap(long, default_value = "")]
     pub game_info: String,
 
+    /// Pokédex entry at this position among the games sorted by name (wraps around)
+    #[clap(long)]
+    pub desc_index: Option<usize>,
+
+    /// Truncate the Pokédex entry to this many lines, ending with an ellipsis
+    #[clap(long, value_parser = clap::value_parser!(u16).range(1..))]
+    pub desc_max_lines: Option<u16>,
+
     /// Do not display Pokémon name
     #[clap(long)]
     pub no_title: bool,
 
     /// Description under or not
-    #[clap(short, long)]
+    #[clap(short, long, overrides_with = "no_under")]
     pub under: bool,
 
+    /// Show the description beside the art, even if the config puts it under by default
+    #[clap(long, overrides_with = "under")]
+    pub no_under: bool,
+
     /// Left padding
     #[clap(long, default_value = "0")]
     pub padding_left: usize,
 
+    /// Blank lines before the art
+    #[clap(long, default_value = "0")]
+    pub padding_top: usize,
+
+    /// Draw a box around the art and description
+    #[clap(long)]
+    pub frame: bool,
+
+    /// Flip the art horizontally
+    #[clap(long)]
+    pub mirror: bool,
+
+    /// Use 256 colors instead of 24-bit colors, for terminals without truecolor
+    #[clap(long = "256color")]
+    pub color256: bool,
+
+    /// Recolor the art with a palette
+    #[clap(long, value_enum)]
+    pub palette: Option<Palette>,
+
+    /// Draw the art with ASCII characters only, in a single color, for terminals without block glyphs
+    #[clap(long)]
+    pub ascii: bool,
+
+    /// Reveal the art line by line, waiting this many milliseconds between lines (terminal only)
+    #[clap(long)]
+    pub delay: Option<u64>,
+
+    /// Cycle through the frames of animated art every MS milliseconds [default: 200] until interrupted (terminal only)
+    #[clap(long, value_name = "MS", num_args = 0..=1, default_missing_value = "200")]
+    pub animate: Option<u64>,
+
+    /// Write the output to this file instead of standard output (see --no-color for plain text)
+    #[clap(long)]
+    pub output_file: Option<PathBuf>,
+
     /// Show Pokémon stats
     #[clap(long)]
     pub stats: bool,
 
-    /// Show if the pokemon is already been encountered
+    /// Show Pokémon stats as a hexagonal radar chart instead of a grid
+    #[clap(long)]
+    pub radar: bool,
+
+    /// Show the Pokémon types next to its name (always shown with --info)
+    #[clap(long)]
+    pub show_types: bool,
+
+    /// Show the National Dex number before the Pokémon name, e.g. #0025
+    #[clap(long)]
+    pub show_dex: bool,
+
+    /// Show if the pokemon is already been encountered (with --track)
     #[clap(long)]
     pub unique: bool,
+
+    /// Print the Pokédex entry in the terminal's default color instead of the configured one
+    #[clap(long)]
+    pub no_desc_color: bool,
+
+    /// Do not print the game the Pokédex entry comes from under it
+    #[clap(long)]
+    pub no_desc_source: bool,
+
+    /// Do not print the notice shown with --info when the Pokémon has no Pokédex entry
+    #[clap(long)]
+    pub quiet: bool,
+
+    /// Paint a background behind the art: a color name (e.g. blue, bright_black) or a 256-color index
+    #[clap(long, value_name = "COLOR")]
+    pub bg: Option<TextColor>,
+
+    /// List the forms with art and whether they have shiny art, then exit
+    #[clap(long)]
+    pub list_forms: bool,
+
+    /// Record the Pokémon as encountered in the Pokédex tracker
+    #[clap(long)]
+    pub track: bool,
+}
+
+/// Represents the options for listing Pokémon.
+///
+/// # Fields
+/// - `detailed`: A flag indicating whether to print the generation and forms next to each slug.
+/// - `page`: A flag indicating whether to show the list in a pager when printing to a terminal.
+/// - `dex`: An optional National Dex range (1-151) the list is limited to.
+/// - `count`: A flag indicating whether to print the number of Pokémon instead of their slugs.
+/// - `by_gen`: A flag indicating whether the count is broken down by generation.
+/// - `species_only`: A flag indicating whether entries that are a form of another entry are left out.
+#[derive(Debug, Args)]
+pub struct List {
+    /// Print slug, generation and forms as tab-separated columns
+    #[clap(short, long)]
+    pub detailed: bool,
+
+    /// Show the list in $PAGER (less by default) when printing to a terminal
+    #[clap(short, long)]
+    pub page: bool,
+
+    /// Only list the Pokémon in this National Dex range (1-151) or with this number
+    #[clap(long, alias = "dex-range")]
+    pub dex: Option<String>,
+
+    /// Print the number of Pokémon instead of the list
+    #[clap(long, conflicts_with = "detailed")]
+    pub count: bool,
+
+    /// With --count, print the number of Pokémon in every generation, then the total
+    #[clap(long, requires = "count")]
+    pub by_gen: bool,
+
+    /// Collapse the entries that are a form of another entry into that species
+    #[clap(long, alias = "dedupe-forms")]
+    pub species_only: bool,
+}
+
+/// Represents the options for showing the shiny log.
+///
+/// # Fields
+/// - `since`: An optional date; only the entries logged on or after it are shown.
+#[derive(Debug, Args)]
+pub struct ShowShiny {
+    /// Only show shiny caught on or after this date (YYYY-MM-DD)
+    #[clap(long)]
+    pub since: Option<NaiveDate>,
+}
+
+/// Represents the options for logging a shiny capture.
+///
+/// # Fields
+/// - `name`: The slug of the Pokémon caught.
+/// - `form`: The form of the Pokémon caught.
+/// - `encounters`: An optional number of encounters the shiny took.
+/// - `details`: Free text stored with the capture (game, method...).
+#[derive(Debug, Args)]
+pub struct Catch {
+    /// Name of the Pokémon caught
+    pub name: String,
+
+    /// Form of the Pokémon caught
+    #[clap(short, long, default_value = "regular")]
+    pub form: String,
+
+    /// Number of encounters the shiny took
+    #[clap(long)]
+    pub encounters: Option<u32>,
+
+    /// Free text stored with the capture, e.g. the game or the hunting method
+    #[clap(long, default_value = "")]
+    pub details: String,
+}
+
+/// Represents the options for printing a Pokédex entry without art.
+///
+/// # Fields
+/// - `name`: The name of the Pokémon to describe.
+/// - `game_info`: The specific game for which to retrieve the Pokédex entry, possibly abbreviated.
+/// - `desc_index`: An optional position selecting the Pokédex entry deterministically.
+/// - `lang`: An optional language overriding the configured one.
+#[derive(Debug, Args)]
+pub struct Describe {
+    /// Name of the Pokémon to describe
+    pub name: String,
+
+    /// Pokédex entry for a specific game, or the start or part of its name (e.g. omega)
+    #[clap(long, default_value = "")]
+    pub game_info: String,
+
+    /// Pokédex entry at this position among the games sorted by name (wraps around)
+    #[clap(long)]
+    pub desc_index: Option<usize>,
+
+    /// Language of the Pokédex entry, overriding the configured one
+    #[clap(long)]
+    pub lang: Option<String>,
+}
+
+/// Represents the options for writing the raw art of a Pokémon.
+///
+/// # Fields
+/// - `name`: The name of the Pokémon whose art is written.
+/// - `form`: The form whose art is written, `regular` for the base form.
+/// - `shiny`: A flag indicating whether to write the shiny art.
+#[derive(Debug, Args)]
+pub struct Sprite {
+    /// Name of the Pokémon
+    pub name: String,
+
+    /// Form of the Pokémon, e.g. mega or alola
+    #[clap(short, long, default_value = "regular", value_parser = FormValueParser)]
+    pub form: String,
+
+    /// Write the shiny art instead
+    #[clap(short, long)]
+    pub shiny: bool,
+}
+
+/// Represents the options for showing two Pokémon facing each other.
+///
+/// # Fields
+/// - `left`: The name of the Pokémon shown on the left.
+/// - `right`: The name of the Pokémon shown on the right, mirrored to face the left one.
+/// - `gap`: The number of columns between the two arts.
+/// - `shiny`: A flag indicating whether to show the shiny art of both Pokémon.
+/// - `no_title`: A flag that, if set, will prevent displaying the names above the arts.
+#[derive(Debug, Args)]
+pub struct Battle {
+    /// Name of the Pokémon on the left
+    pub left: String,
+
+    /// Name of the Pokémon on the right, facing the left one
+    pub right: String,
+
+    /// Number of columns between the two Pokémon
+    #[clap(long, default_value = "4")]
+    pub gap: usize,
+
+    /// Show the shiny version of both Pokémon
+    #[clap(short, long)]
+    pub shiny: bool,
+
+    /// Do not display the names above the Pokémon
+    #[clap(long)]
+    pub no_title: bool,
+}
+
+/// Represents the options for counting the encounters of a shiny hunt.
+///
+/// # Fields
+/// - `name`: The slug of the Pokémon hunted.
+/// - `reset`: A flag indicating whether to end the hunt instead of counting an encounter.
+#[derive(Debug, Args)]
+pub struct Hunt {
+    /// Name of the Pokémon hunted
+    pub name: String,
+
+    /// End the hunt, forgetting its counter
+    #[clap(long)]
+    pub reset: bool,
+}
+
+/// Represents the options for computing the odds of a shiny hunt.
+///
+/// # Fields
+/// - `method`: The hunting method whose base rate is used, the configured shiny rate if `None`.
+/// - `encounters`: The number of encounters so far.
+#[derive(Debug, Args)]
+pub struct Odds {
+    /// Hunting method whose base rate is used, instead of the configured shiny rate
+    #[clap(short, long, value_enum)]
+    pub method: Option<HuntMethod>,
+
+    /// Number of encounters so far
+    #[clap(short, long, default_value = "0")]
+    pub encounters: u64,
+}
+
+/// Represents the shiny log maintenance commands.
+#[derive(Debug, Subcommand)]
+pub enum ShinyCommands {
+    /// Rewrite the shiny log keeping only the readable entries (the original is kept as .bak)
+    Repair,
+    /// Print the shiny log as CSV or as a Markdown table, e.g. to share it
+    Export {
+        /// Output format
+        #[clap(short, long, value_enum, default_value = "csv")]
+        format: LogFormat,
+    },
+}
+
+/// Represents the options for maintaining the shiny log.
+///
+/// # Fields
+/// - `command`: The maintenance command to run.
+#[derive(Debug, Args)]
+pub struct Shiny {
+    #[clap(subcommand)]
+    pub command: ShinyCommands,
+}
+
+/// Represents the commands reading and changing a setting.
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommands {
+    /// Print the value of a setting (language, shiny_rate or shiny_log_path)
+    Get {
+        /// Name of the setting
+        key: String,
+    },
+    /// Change a setting in the configuration file
+    Set {
+        /// Name of the setting
+        key: String,
+        /// New value of the setting
+        value: String,
+    },
+}
+
+/// Represents the options for reading and changing the settings.
+///
+/// # Fields
+/// - `command`: The command to run on the settings.
+#[derive(Debug, Args)]
+pub struct Config {
+    #[clap(subcommand)]
+    pub command: ConfigCommands,
+}
+
+/// Represents the options for showing the Pokédex completion.
+///
+/// # Fields
+/// - `json`: A flag indicating whether to print the completion as JSON, including the missing Pokémon.
+#[derive(Debug, Args)]
+pub struct ShowCompletion {
+    /// Print the completion as JSON, with the slugs of the Pokémon not encountered yet
+    #[clap(long)]
+    pub json: bool,
+}
+
+/// Represents the options for printing the version.
+///
+/// # Fields
+/// - `data`: A flag indicating whether to also describe the Pokémon dataset in use.
+#[derive(Debug, Args)]
+pub struct Version {
+    /// Also describe the Pokémon dataset: count, generations, languages, stats and types
+    #[clap(long)]
+    pub data: bool,
+}
+
+/// Represents the options for the "Who's that Pokémon!?" quiz.
+///
+/// # Fields
+/// - `generations`: A string specifying the generation number, range (1-9), or list of generations (1,3,6).
+#[derive(Debug, Args)]
+pub struct WhosThat {
+    /// Generation number, range (1-9), or list of generations (1,3,6)
+    #[clap(default_value = "1-9")]
+    pub generations: String,
+}
+
+/// Represents the options for ranking Pokémon by a stat.
+///
+/// # Fields
+/// - `sort`: The stat to sort by.
+/// - `generations`: A string specifying the generation number, range (1-9), or list of generations (1,3,6).
+/// - `top`: The number of Pokémon to show.
+/// - `type_name`: An optional type the Pokémon must have.
+/// - `average`: A flag indicating whether to print the average stats instead of a ranking.
+#[derive(Debug, Args)]
+pub struct Stats {
+    /// Stat to sort by: hp, attack, defense, special-attack, special-defense, speed or total
+    #[clap(long, default_value = "total")]
+    pub sort: String,
+
+    /// Generation number, range (1-9), or list of generations (1,3,6)
+    #[clap(default_value = "1-9")]
+    pub generations: String,
+
+    /// Number of Pokémon to show
+    #[clap(short = 'n', long, default_value = "10")]
+    pub top: usize,
+
+    /// Only include Pokémon of this type (e.g. fire)
+    #[clap(long = "type", value_name = "TYPE")]
+    pub type_name: Option<String>,
+
+    /// Print the average stats of the Pokémon instead of ranking them
+    #[clap(long)]
+    pub average: bool,
+}
+
+/// Represents the options for exporting the Pokédex.
+///
+/// # Fields
+/// - `format`: The output format, either CSV or a pretty-printed JSON array.
+/// - `dex`: An optional National Dex range (1-151) the export is limited to.
+/// - `species_only`: A flag indicating whether entries that are a form of another entry are left out.
+#[derive(Debug, Args)]
+pub struct Export {
+    /// Output format
+    #[clap(short, long, value_enum, default_value = "csv")]
+    pub format: Format,
+
+    /// Only export the Pokémon in this National Dex range (1-151) or with this number
+    #[clap(long, alias = "dex-range")]
+    pub dex: Option<String>,
+
+    /// Collapse the entries that are a form of another entry into that species
+    #[clap(long, alias = "dedupe-forms")]
+    pub species_only: bool,
 }
 
 /// Represents options for showing a random Pokémon.
 ///
 /// # Fields
 /// - `generations`: A string specifying the generation number, range (1-9), or list of generations (1,3,6).
+/// - `min_gen`: An optional first generation, building a range with `max_gen` instead of `generations`.
+/// - `max_gen`: An optional last generation, building a range with `min_gen` instead of `generations`.
+/// - `include_unknown_gen`: A flag indicating whether the Pokémon without a generation may be picked.
 /// - `info`: A flag indicating whether to print the Pokédex entry if it exists.
-/// - `game_info`: The specific game for which to retrieve the Pokédex entry.
+/// - `no_info`: A flag turning off the Pokédex entry the config prints by default.
+/// - `game_info`: The specific game for which to retrieve the Pokédex entry, possibly abbreviated.
+/// - `desc_index`: An optional position selecting the Pokédex entry deterministically.
+/// - `desc_max_lines`: An optional number of lines the Pokédex entry is truncated to.
 /// - `shiny`: A flag indicating whether to show the shiny version of the Pokémon.
+/// - `no_shiny`: A flag turning off the shiny version the config shows by default.
+/// - `shiny_fallback`: A flag indicating whether to show the regular art when there is no shiny art.
+/// - `shiny_chance`: An optional probability overriding the configured shiny rate for this run.
 /// - `no_title`: A flag that, if set, will prevent displaying the Pokémon's name.
 /// - `under`: A flag indicating whether to display the description under the Pokémon art.
+/// - `no_under`: A flag turning off the description under the art the config sets by default.
 /// - `no_mega`: A flag indicating whether to exclude mega Pokémon.
 /// - `no_gmax`: A flag indicating whether to exclude Gigantamax Pokémon.
 /// - `no_regional`: A flag indicating whether to exclude regional forms.
+/// - `legendary`: A flag restricting the pick to legendary Pokémon (and mythical ones with `mythical`).
+/// - `mythical`: A flag restricting the pick to mythical Pokémon (and legendary ones with `legendary`).
+/// - `exclude`: The slugs of the Pokémon that must never be picked.
+/// - `from_file`: An optional file listing the only Pokémon that may be picked, one slug per line.
 /// - `padding_left`: An integer specifying the amount of left padding for display.
+/// - `padding_top`: An integer specifying the number of blank lines printed before the art.
+/// - `frame`: A flag indicating whether to draw a box around the art and description.
+/// - `mirror`: A flag indicating whether to flip the art horizontally.
+/// - `color256`: A flag indicating whether to convert the art to the 256-color palette.
+/// - `delay`: An optional number of milliseconds to wait between lines, to animate the art.
+/// - `animate`: An optional number of milliseconds each frame of animated art is shown for.
+/// - `output_file`: An optional file the output is written to instead of standard output.
+/// - `palette`: An optional palette the art is recolored with (grayscale, sepia or invert).
+/// - `ascii`: A flag indicating whether to draw the art with ASCII characters instead of block glyphs.
 /// - `stats`: A flag indicating whether to show the Pokémon's stats.
+/// - `radar`: A flag indicating whether to show the Pokémon's stats as a radar chart.
+/// - `show_types`: A flag indicating whether to show the Pokémon's types in the header.
+/// - `show_dex`: A flag indicating whether to show the National Dex number in the header.
 /// - `unique`: A flag inficating wheter the pokemon is unique or not.
+/// - `no_desc_color`: A flag indicating whether to print the Pokédex entry in the terminal's default color.
+/// - `no_desc_source`: A flag indicating whether to leave out the game the Pokédex entry comes from.
+/// - `quiet`: A flag indicating whether to leave out the notice shown when there is no Pokédex entry.
+/// - `bg`: An optional color painted behind the art.
+/// - `count`: The number of Pokémon to show, one after the other.
+/// - `progress`: A flag indicating whether to report each completed pick on standard error.
+/// - `unique_run`: A flag indicating whether a Pokémon may only be shown once per invocation.
+/// - `repeat`: An optional number of seconds after which the screen is cleared and a new pick shown.
+/// - `max_retries`: An optional number of picks tried per Pokémon shown, overriding the config.
 #[derive(Debug, Args)]
 pub struct Random {
     /// Generation number, range (1-9), or list of generations (1,3,6)
     #[clap(default_value = "1-9")]
     pub generations: String,
 
+    /// First generation to pick from, overriding the generations argument
+    #[clap(long)]
+    pub min_gen: Option<u8>,
+
+    /// Last generation to pick from, overriding the generations argument
+    #[clap(long)]
+    pub max_gen: Option<u8>,
+
+    /// Also pick the Pokémon the database gives no generation for
+    #[clap(long)]
+    pub include_unknown_gen: bool,
+
     /// Print Pokédex entry (if it exists)
-    #[clap(short, long)]
+    #[clap(short, long, overrides_with = "no_info")]
     pub info: bool,
 
+    /// Do not print the Pokédex entry, even if the config prints it by default
+    #[clap(long, overrides_with = "info")]
+    pub no_info: bool,
+
     #[clap(long, default_value = "")]
     pub game_info: String,
 
+    /// Pokédex entry at this position among the games sorted by name (wraps around)
+    #[clap(long)]
+    pub desc_index: Option<usize>,
+
+    /// Truncate the Pokédex entry to this many lines, ending with an ellipsis
+    #[clap(long, value_parser = clap::value_parser!(u16).range(1..))]
+    pub desc_max_lines: Option<u16>,
+
     /// Show the shiny version of the Pokémon instead
-    #[clap(short, long)]
+    #[clap(short, long, overrides_with = "no_shiny")]
     pub shiny: bool,
 
+    /// Show the regular version, even if the config shows the shiny one by default
+    #[clap(long, overrides_with = "shiny")]
+    pub no_shiny: bool,
+
+    /// Show the regular art, with a note, when the Pokémon has no shiny art
+    #[clap(long)]
+    pub shiny_fallback: bool,
+
+    /// Probability (0.0 to 1.0) of a shiny, overriding the configured shiny rate
+    #[clap(long)]
+    pub shiny_chance: Option<f64>,
+
     /// Do not display Pokémon name
     #[clap(long)]
     pub no_title: bool,
 
     /// Description under or not
-    #[clap(short, long)]
+    #[clap(short, long, overrides_with = "no_under")]
     pub under: bool,
 
+    /// Show the description beside the art, even if the config puts it under by default
+    #[clap(long, overrides_with = "under")]
+    pub no_under: bool,
+
     /// Do not show mega Pokémon
     #[clap(long)]
     pub no_mega: bool,
@@ -126,24 +716,164 @@ pub struct Random {
     #[clap(long)]
     pub no_regional: bool,
 
+    /// Only show legendary Pokémon (combined with --mythical, legendary or mythical ones)
+    #[clap(long)]
+    pub legendary: bool,
+
+    /// Only show mythical Pokémon (combined with --legendary, legendary or mythical ones)
+    #[clap(long)]
+    pub mythical: bool,
+
+    /// Never show these Pokémon (comma-separated slugs)
+    #[clap(long, value_delimiter = ',')]
+    pub exclude: Vec<String>,
+
+    /// Only show the Pokémon listed in this file, one slug per line
+    #[clap(long, value_name = "PATH")]
+    pub from_file: Option<PathBuf>,
+
     /// Left padding
     #[clap(long, default_value = "0")]
     pub padding_left: usize,
 
+    /// Blank lines before the art
+    #[clap(long, default_value = "0")]
+    pub padding_top: usize,
+
+    /// Draw a box around the art and description
+    #[clap(long)]
+    pub frame: bool,
+
+    /// Flip the art horizontally
+    #[clap(long)]
+    pub mirror: bool,
+
+    /// Use 256 colors instead of 24-bit colors, for terminals without truecolor
+    #[clap(long = "256color")]
+    pub color256: bool,
+
+    /// Recolor the art with a palette
+    #[clap(long, value_enum)]
+    pub palette: Option<Palette>,
+
+    /// Draw the art with ASCII characters only, in a single color, for terminals without block glyphs
+    #[clap(long)]
+    pub ascii: bool,
+
+    /// Reveal the art line by line, waiting this many milliseconds between lines (terminal only)
+    #[clap(long)]
+    pub delay: Option<u64>,
+
+    /// Cycle through the frames of animated art every MS milliseconds [default: 200] until interrupted (terminal only)
+    #[clap(long, value_name = "MS", num_args = 0..=1, default_missing_value = "200")]
+    pub animate: Option<u64>,
+
+    /// Write the output to this file instead of standard output (see --no-color for plain text)
+    #[clap(long)]
+    pub output_file: Option<PathBuf>,
+
     /// Show Pokémon stats
     #[clap(long)]
     pub stats: bool,
 
+    /// Show Pokémon stats as a hexagonal radar chart instead of a grid
+    #[clap(long)]
+    pub radar: bool,
+
+    /// Show the Pokémon types next to its name (always shown with --info)
+    #[clap(long)]
+    pub show_types: bool,
+
+    /// Show the National Dex number before the Pokémon name, e.g. #0025
+    #[clap(long)]
+    pub show_dex: bool,
+
     /// Show if the pokemon is already been encountered
     #[clap(long)]
     pub unique: bool,
+
+    /// Print the Pokédex entry in the terminal's default color instead of the configured one
+    #[clap(long)]
+    pub no_desc_color: bool,
+
+    /// Do not print the game the Pokédex entry comes from under it
+    #[clap(long)]
+    pub no_desc_source: bool,
+
+    /// Do not print the notice shown with --info when the Pokémon has no Pokédex entry
+    #[clap(long)]
+    pub quiet: bool,
+
+    /// Paint a background behind the art: a color name (e.g. blue, bright_black) or a 256-color index
+    #[clap(long, value_name = "COLOR")]
+    pub bg: Option<TextColor>,
+
+    /// Number of Pokémon to show
+    #[clap(long, default_value = "1", value_parser = clap::value_parser!(u32).range(1..))]
+    pub count: u32,
+
+    /// Report each completed pick on standard error
+    #[clap(long)]
+    pub progress: bool,
+
+    /// Never show the same Pokémon twice with --count, allowing repeats again once all were shown
+    #[clap(long)]
+    pub unique_run: bool,
+
+    /// Clear the screen and show a new pick every SECONDS seconds, until Ctrl-C (terminal only)
+    #[clap(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub repeat: Option<u64>,
+
+    /// Number of picks tried before giving up when they have no art to show, overriding the config
+    #[clap(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_retries: Option<u32>,
+}
+
+impl Random {
+    /// Returns the generations to pick from, as typed in the generations argument.
+    ///
+    /// With `--min-gen` or `--max-gen`, this is the range they delimit, the missing
+    /// bound being the first or last generation, so it is validated the same way.
+    pub fn generations(&self) -> String {
+        match (self.min_gen, self.max_gen) {
+            (None, None) => self.generations.clone(),
+            (min, max) => format!(
+                "{}-{}",
+                min.unwrap_or(crate::MIN_GENERATION),
+                max.unwrap_or(crate::MAX_GENERATION)
+            ),
+        }
+    }
+}
+
+/// Represents how often the Pokémon shown by the daily command changes.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Period {
+    Hourly,
+    Daily,
+    Weekly,
+}
+
+/// Represents options for showing the Pokémon of the period.
+///
+/// # Fields
+/// - `random`: The selection and display options, shared with the random command.
+/// - `period`: How often the Pokémon changes.
+#[derive(Debug, Args)]
+pub struct Daily {
+    #[clap(flatten)]
+    pub random: Random,
+
+    /// How often the Pokémon changes
+    #[clap(long, value_enum, default_value = "daily")]
+    pub period: Period,
 }
 
 /// Represents the various commands available in the CLI.
 #[derive(Debug, Subcommand)]
 pub enum Commands {
     /// Print a list of all Pokémon
-    List,
+    List(crate::cli::List),
     /// Select Pokémon by name. Generally spelled like in the games.
     /// A few exceptions are nidoran-f, nidoran-m, mr-mime, farfetchd,
     /// flabebe type-null etc. Perhaps grep the output of list if in doubt.
@@ -153,12 +883,48 @@ pub enum Commands {
     /// generation or range of generations. The generations can be provided as
     /// a continuous range (e.g., 1-3) or as a list of generations (1,3,6).
     Random(crate::cli::Random),
+    /// Show the Pokémon of the day. Takes the same options as random, but
+    /// every invocation on the same day shows the same Pokémon. With --period,
+    /// the Pokémon changes every hour or every week instead.
+    Daily(crate::cli::Daily),
+    /// Print the Pokédex entry of a Pokémon, without its art
+    Describe(crate::cli::Describe),
+    /// Write the art of a Pokémon exactly as stored, without name, padding or any change
+    Sprite(crate::cli::Sprite),
+    /// Show two Pokémon side by side, facing each other
+    Battle(crate::cli::Battle),
+    /// Who's that Pokémon!? Guess a random Pokémon from its silhouette
+    #[clap(name = "whosthat")]
+    WhosThat(crate::cli::WhosThat),
     /// Generate shell completions
     Init(crate::cli::ShellName),
+    /// Rank the Pokémon by a base stat, highest first, or average their stats
+    Stats(crate::cli::Stats),
+    /// Export slug, generation, English name and stats of every Pokémon
+    Export(crate::cli::Export),
+    /// Log a shiny capture in the shiny log
+    Catch(crate::cli::Catch),
+    /// Count an encounter in the shiny hunt for a Pokémon
+    Hunt(crate::cli::Hunt),
+    /// Compute the chance of having found a shiny after some encounters, and the encounters
+    /// needed for 50%, 90% and 99%
+    Odds(crate::cli::Odds),
+    /// Show today's shiny hunting: active hunts, shiny caught today and in total
+    Today,
     /// Show shiny
-    ShowShiny,
+    ShowShiny(crate::cli::ShowShiny),
+    /// Maintain the shiny log
+    Shiny(crate::cli::Shiny),
+    /// Print the version, and with --data the Pokémon dataset in use
+    Version(crate::cli::Version),
+    /// Print where the configuration, shiny log and Pokédex tracker are stored
+    Paths,
+    /// Check that every Pokémon has regular and shiny art, stats and an English name
+    Doctor,
+    /// Read or change a setting without editing the configuration file
+    Config(crate::cli::Config),
     /// Show pokedex completions
-    ShowCompletion,
+    ShowCompletion(crate::cli::ShowCompletion),
 }
 
 /// Builds the command structure for the CLI, including subcommands and common arguments.
@@ -167,13 +933,38 @@ pub enum Commands {
 /// - `Command`: The built command structure, ready for use in the CLI.
 pub fn build() -> Command {
     let common_args = [
-        arg!(-i --info "Print Pokédex entry (if it exists)"),
-        arg!(-s --shiny "Show the shiny Pokémon version instead"),
+        arg!(-i --info "Print Pokédex entry (if it exists)").overrides_with("no-info"),
+        arg!(--"no-info" "Do not print the Pokédex entry, even if the config prints it by default")
+            .overrides_with("info"),
+        arg!(-s --shiny "Show the shiny Pokémon version instead").overrides_with("no-shiny"),
+        arg!(--"no-shiny" "Show the regular version, even if the config shows the shiny one by default")
+            .overrides_with("shiny"),
+        arg!(--"shiny-fallback" "Show the regular art, with a note, when the Pokémon has no shiny art"),
         arg!(--"game-info" "Choose a game for the Pokédex entry"),
+        arg!(--"desc-index" <INDEX> "Choose the Pokédex entry by position among the sorted games"),
+        arg!(--"desc-max-lines" <N> "Truncate the Pokédex entry to this many lines"),
         arg!(--"no-title" "Do not display Pokémon name"),
         arg!(--"padding-left" "Set amount of padding to the left [default: 0]"),
-        arg!(-u --under "Show the Pokédex entry under the Pokémon"),
+        arg!(--"padding-top" "Set amount of blank lines before the art [default: 0]"),
+        arg!(-u --under "Show the Pokédex entry under the Pokémon").overrides_with("no-under"),
+        arg!(--"no-under" "Show the Pokédex entry beside the art, even if the config puts it under by default")
+            .overrides_with("under"),
+        arg!(--frame "Draw a box around the art and description"),
+        arg!(--mirror "Flip the art horizontally"),
+        arg!(--"256color" "Use 256 colors instead of 24-bit colors"),
+        arg!(--palette <PALETTE> "Recolor the art: grayscale, sepia or invert"),
+        arg!(--ascii "Draw the art with ASCII characters only, for terminals without block glyphs"),
+        arg!(--delay <MS> "Reveal the art line by line, waiting MS milliseconds between lines"),
+        arg!(--animate [MS] "Cycle through the frames of animated art every MS milliseconds [default: 200]"),
+        arg!(--"output-file" <PATH> "Write the output to this file instead of standard output"),
         arg!(--stats "Show the Pokémon stats"),
+        arg!(--radar "Show the Pokémon stats as a hexagonal radar chart instead of a grid"),
+        arg!(--"show-types" "Show the Pokémon types next to its name"),
+        arg!(--"show-dex" "Show the National Dex number before the Pokémon name"),
+        arg!(--"no-desc-color" "Print the Pokédex entry in the terminal's default color"),
+        arg!(--"no-desc-source" "Do not print the game the Pokédex entry comes from under it"),
+        arg!(--quiet "Do not print the notice shown when the Pokémon has no Pokédex entry"),
+        arg!(--bg <COLOR> "Paint a background behind the art: a color name or a 256-color index"),
     ];
     let init = Command::new("init")
         .about("Generate shell completions")
@@ -184,10 +975,45 @@ pub fn build() -> Command {
             arg!(["powershell"]),
             arg!(["elvish"]),
         ]);
-    let list = Command::new("list").about("List all names of Pokémon");
+    let export = Command::new("export")
+        .about("Export slug, generation, English name and stats of every Pokémon")
+        .args([
+            arg!(-f --format <FORMAT> "Output format: csv or json [default: csv]"),
+            arg!(--dex <RANGE> "Only export this National Dex range (1-151)"),
+            arg!(--"species-only" "Collapse the entries that are a form of another entry into that species")
+                .alias("dedupe-forms"),
+        ]);
+    let stats = Command::new("stats")
+        .about("Rank the Pokémon by a base stat, highest first, or average their stats")
+        .arg(arg!([GENERATIONS] "Generation number, range (1-9), or list of generations (1,3,6) [default: 1-9]"))
+        .args([
+            arg!(--sort <STAT> "Stat to sort by [default: total]"),
+            arg!(-n --top <N> "Number of Pokémon to show [default: 10]"),
+            arg!(--type <TYPE> "Only include Pokémon of this type (e.g. fire)"),
+            arg!(--average "Print the average stats of the Pokémon instead of ranking them"),
+        ]);
+    let list = Command::new("list")
+        .about("List all names of Pokémon")
+        .args([
+            arg!(-d --detailed "Print slug, generation and forms as tab-separated columns"),
+            arg!(-p --page "Show the list in $PAGER (less by default) when printing to a terminal"),
+            arg!(--dex <RANGE> "Only list this National Dex range (1-151)"),
+            arg!(--count "Print the number of Pokémon instead of the list")
+                .conflicts_with("detailed"),
+            arg!(--"by-gen" "With --count, print the number of Pokémon in every generation, then the total")
+                .requires("count"),
+            arg!(--"species-only" "Collapse the entries that are a form of another entry into that species")
+                .alias("dedupe-forms"),
+        ]);
     let name = Command::new("name")
         .about("Select Pokémon by name: e.g., 'pikachu'")
         .arg(arg!([name] "Who's that Pokémon!?"))
+        .arg(
+            arg!(-f --form <FORM> "Show an alternative form of the Pokémon")
+                .value_parser(FormValueParser),
+        )
+        .arg(arg!(--"list-forms" "List the forms with art and whether they have shiny art"))
+        .arg(arg!(--track "Record the Pokémon as encountered in the Pokédex tracker"))
         .args(&common_args);
 
     let random = Command::new("random")
@@ -197,12 +1023,134 @@ pub fn build() -> Command {
         )
         .args(common_args)
         .args([
+            arg!(--"min-gen" <GEN> "First generation to pick from, overriding the generations argument"),
+            arg!(--"max-gen" <GEN> "Last generation to pick from, overriding the generations argument"),
+            arg!(--"include-unknown-gen" "Also pick the Pokémon the database gives no generation for"),
+            arg!(--"shiny-chance" <CHANCE> "Probability (0.0 to 1.0) of a shiny, overriding the config"),
             arg!(--"no-mega" "Do not show mega Pokémon"),
             arg!(--"no-gmax" "Do not show Gigantamax Pokémon"),
             arg!(--"no-regional" "Do not show regional Pokémon"),
+            arg!(--legendary "Only show legendary Pokémon"),
+            arg!(--mythical "Only show mythical Pokémon"),
+            arg!(--exclude <SLUGS> "Never show these Pokémon (comma-separated slugs)"),
+            arg!(--"from-file" <PATH> "Only show the Pokémon listed in this file, one slug per line"),
+            arg!(--count <N> "Number of Pokémon to show [default: 1]"),
+            arg!(--progress "Report each completed pick on standard error"),
+            arg!(--"unique-run" "Never show the same Pokémon twice with --count"),
+            arg!(--repeat <SECONDS> "Clear the screen and show a new pick every SECONDS seconds, until Ctrl-C"),
+            arg!(--"max-retries" <N> "Number of picks tried before giving up when they have no art to show"),
         ]);
+    let daily = random
+        .clone()
+        .name("daily")
+        .about("Show the Pokémon of the day")
+        .arg(
+            arg!(--period <PERIOD> "How often the Pokémon changes [default: daily]")
+                .value_parser(["hourly", "daily", "weekly"]),
+        );
 
-    Command::new("kingler").subcommands([init, list, name, random])
+    let catch = Command::new("catch")
+        .about("Log a shiny capture in the shiny log")
+        .arg(arg!(<name> "Name of the Pokémon caught"))
+        .args([
+            arg!(-f --form <FORM> "Form of the Pokémon caught [default: regular]"),
+            arg!(--encounters <N> "Number of encounters the shiny took"),
+            arg!(--details <TEXT> "Free text stored with the capture"),
+        ]);
+    let describe = Command::new("describe")
+        .about("Print the Pokédex entry of a Pokémon, without its art")
+        .arg(arg!(<name> "Name of the Pokémon to describe"))
+        .args([
+            arg!(--"game-info" <GAME> "Pokédex entry for a specific game"),
+            arg!(--"desc-index" <INDEX> "Choose the Pokédex entry by position among the sorted games"),
+            arg!(--lang <CODE> "Language of the Pokédex entry, overriding the configured one"),
+        ]);
+    let sprite = Command::new("sprite")
+        .about("Write the art of a Pokémon exactly as stored, without name, padding or any change")
+        .arg(arg!(<name> "Name of the Pokémon"))
+        .args([
+            arg!(-f --form <FORM> "Form of the Pokémon [default: regular]")
+                .value_parser(FormValueParser),
+            arg!(-s --shiny "Write the shiny art instead"),
+        ]);
+    let battle = Command::new("battle")
+        .about("Show two Pokémon side by side, facing each other")
+        .args([
+            arg!(<left> "Name of the Pokémon on the left"),
+            arg!(<right> "Name of the Pokémon on the right, facing the left one"),
+            arg!(--gap <COLUMNS> "Number of columns between the two Pokémon [default: 4]"),
+            arg!(-s --shiny "Show the shiny version of both Pokémon"),
+            arg!(--"no-title" "Do not display the names above the Pokémon"),
+        ]);
+    let hunt = Command::new("hunt")
+        .about("Count an encounter in the shiny hunt for a Pokémon")
+        .arg(arg!(<name> "Name of the Pokémon hunted"))
+        .arg(arg!(--reset "End the hunt, forgetting its counter"));
+    let odds = Command::new("odds")
+        .about("Compute the chance of having found a shiny after some encounters")
+        .args([
+            arg!(-m --method <METHOD> "Hunting method whose base rate is used, instead of the configured shiny rate")
+                .value_parser(["full-odds", "shiny-charm", "masuda", "masuda-charm"]),
+            arg!(-e --encounters <N> "Number of encounters so far [default: 0]"),
+        ]);
+    let today = Command::new("today")
+        .about("Show today's shiny hunting: active hunts, shiny caught today and in total");
+    let shiny = Command::new("shiny")
+        .about("Maintain the shiny log")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("repair").about("Rewrite the shiny log keeping only the readable entries"),
+        )
+        .subcommand(
+            Command::new("export")
+                .about("Print the shiny log as CSV or as a Markdown table")
+                .arg(
+                    arg!(-f --format <FORMAT> "Output format: csv or md [default: csv]")
+                        .value_parser(["csv", "md"]),
+                ),
+        );
+    let version = Command::new("version")
+        .about("Print the version, and with --data the Pokémon dataset in use")
+        .arg(arg!(--data "Also describe the Pokémon dataset"));
+    let paths = Command::new("paths")
+        .about("Print where the configuration, shiny log and Pokédex tracker are stored");
+    let doctor = Command::new("doctor")
+        .about("Check that every Pokémon has regular and shiny art, stats and an English name");
+    let config = Command::new("config")
+        .about("Read or change a setting without editing the configuration file")
+        .subcommand_required(true)
+        .subcommands([
+            Command::new("get")
+                .about("Print the value of a setting (language, shiny_rate or shiny_log_path)")
+                .arg(arg!(<key> "Name of the setting")),
+            Command::new("set")
+                .about("Change a setting in the configuration file")
+                .arg(arg!(<key> "Name of the setting"))
+                .arg(arg!(<value> "New value of the setting")),
+        ]);
+    let whosthat = Command::new("whosthat")
+        .about("Guess a random Pokémon from its silhouette")
+        .arg(arg!([GENERATIONS] "Generation number, range (1-9), or list of generations (1,3,6) [default: 1-9]"));
+
+    Command::new("kingler")
+        .arg(
+            arg!(--seed <SEED> "Seed for the random choices, to get reproducible output")
+                .global(true),
+        )
+        .arg(arg!(--"no-color" "Never print colors, even to a terminal").global(true))
+        .arg(
+            arg!(--width <COLUMNS> "Lay the output out for this many columns instead of the terminal's width")
+                .global(true),
+        )
+        .arg(
+            arg!(--db <PATH> "Pokémon database (pokemon.json, optionally gzip-compressed) to use instead of the embedded one")
+                .value_parser(clap::value_parser!(PathBuf))
+                .value_hint(clap::ValueHint::FilePath)
+                .global(true),
+        )
+        .subcommands([
+            init, export, list, name, random, daily, describe, sprite, battle, whosthat, stats, catch, hunt, odds, today, shiny, version, paths, doctor, config,
+        ])
 }
 
 /// Prints the completions for the specified command to the standard output.
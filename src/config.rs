@@ -2,106 +2,734 @@ use serde::{Deserialize, Serialize};
 
 use dirs::home_dir;
 
+use std::collections::HashMap;
 use std::env;
+use std::fmt;
 use std::fs;
-use std::io::ErrorKind::NotFound;
+use std::io::{
+    self,
+    ErrorKind::{NotADirectory, NotFound},
+};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use crate::error::Error;
+use crate::language::Language;
+use crate::pokemon::{Pokemon, PokemonDb};
 
 const BINARY_NAME: &str = env!("CARGO_PKG_NAME");
+const CONFIG_ENV_VAR: &str = "KINGLER_CONFIG";
+
+/// The weight of the forms other than the regular one without a configured weight,
+/// so random pulls are mostly regular with the occasional mega or regional form.
+const DEFAULT_ALTERNATE_FORM_WEIGHT: f64 = 0.1;
 
 /// Represents the configuration settings for the Pokémon application.
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Config {
-    /// Language used when printing Pokémon names and descriptions.
+    /// Language used when printing Pokémon names and descriptions, one of the
+    /// codes supported by [`Language`].
     pub language: String,
 
+    /// Languages tried in order when a Pokémon has no name or description in `language`,
+    /// e.g. `["de", "en"]` for a partially translated dataset.
+    pub language_fallback: Vec<String>,
+
     /// The probability of displaying a shiny Pokémon with the random command.
     pub shiny_rate: f64,
 
     /// The path to the file with the shiny catched
     pub shiny_log_path: String,
+
+    /// Whether every Pokémon shown by the name and random commands is recorded
+    /// in the Pokédex tracker.
+    pub track_encounters: bool,
+
+    /// Color of the Pokédex entries: a color name (e.g. "cyan") or a 256-color index.
+    pub description_color: TextColor,
+
+    /// Directory whose `colorscripts/` and `pokemon.json(.gz)` take precedence
+    /// over the embedded ones, file by file.
+    pub assets_dir: Option<String>,
+
+    /// Relative weights used by the random command, keyed by Pokémon slug or
+    /// generation number. Pokémon without an entry have a weight of 1.
+    pub weights: HashMap<String, f64>,
+
+    /// Relative weights of the forms picked by the random command, keyed by form
+    /// (e.g. "mega", "alola"). Unless configured, the regular form has a weight of 1
+    /// and every other form a weight of `DEFAULT_ALTERNATE_FORM_WEIGHT`.
+    pub form_weights: HashMap<String, f64>,
+
+    /// Thresholds used to color the stats shown with `--stats` by tier.
+    pub stat_tiers: StatTiers,
+
+    /// Whether the National Dex number is shown before the name, as `--show-dex` does.
+    pub show_dex: bool,
+
+    /// Whether the name, random and daily commands print the Pokédex entry unless given
+    /// `--no-info`, as if `--info` was always given.
+    pub default_info: bool,
+
+    /// Whether the name, random and daily commands show the shiny art unless given
+    /// `--no-shiny`, as if `--shiny` was always given.
+    pub default_shiny: bool,
+
+    /// Whether the name, random and daily commands put the Pokédex entry under the art
+    /// unless given `--no-under`, as if `--under` was always given.
+    pub default_under: bool,
+
+    /// Whether the name shown with `--info` is a clickable link, in terminals supporting them.
+    pub link_names: bool,
+
+    /// The page the names link to, `{slug}` being replaced by the Pokémon's slug.
+    pub link_url: String,
+
+    /// Custom names accepted by the name command, ignoring case, each standing for a Pokémon,
+    /// a form and whether it is shiny. Names of actual Pokémon cannot be aliased.
+    pub aliases: HashMap<String, Alias>,
+
+    /// How many Pokémon the random and whosthat commands try before giving up, when the
+    /// ones picked have no art left to show after the filters. `--max-retries` overrides it.
+    pub max_retries: usize,
 }
 
-/// Provides default values for the configuration settings.
-impl Default for Config {
+/// Represents what an alias of the name command stands for.
+///
+/// # Fields
+/// - `slug`: The slug of the Pokémon, e.g. `mewtwo`.
+/// - `form`: The form shown, `regular` unless set.
+/// - `shiny`: Whether the shiny art is shown.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Alias {
+    pub slug: String,
+    #[serde(default = "default_alias_form")]
+    pub form: String,
+    #[serde(default)]
+    pub shiny: bool,
+}
+
+/// Returns the form of an alias without one.
+fn default_alias_form() -> String {
+    "regular".to_string()
+}
+
+/// Represents the thresholds splitting stat values into low, mid and high tiers.
+///
+/// Values under `low` are low, values above `high` are high, and the others are mid.
+/// The total uses the same bands scaled by 6, one per stat.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatTiers {
+    pub low: u32,
+    pub high: u32,
+}
+
+impl Default for StatTiers {
     fn default() -> Self {
-        // Construct the default path for the shiny log file
-        let shiny_log_path = match home_dir() {
-            Some(mut path) => {
-                path.push(".config"); // Ensure you are in the config directory
-                fs::create_dir_all(&path).expect("Failed to create config directory"); // Ensure the directory exists
-                path.push("kingler"); // Add your application-specific directory
-                fs::create_dir_all(&path).expect("Failed to create kingler directory"); // Ensure this directory exists
-                path.push("shiny_log.json"); // Set the filename for the log
-                path.to_str()
-                    .expect("Failed to convert path to string")
-                    .to_string() // Convert PathBuf to String
+        Self { low: 50, high: 90 }
+    }
+}
+
+/// Represents a text color, either by name or as an index in the 256-color palette.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TextColor {
+    Index(u8),
+    Name(String),
+}
+
+/// The named colors, in ANSI order.
+const COLOR_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+impl TextColor {
+    /// Returns the ANSI escape sequence selecting this color as foreground.
+    ///
+    /// Names may be prefixed with `bright_` for the bright variant.
+    ///
+    /// # Returns
+    /// - `Option<String>`: The escape sequence, or `None` for an unknown color name.
+    pub fn escape(&self) -> Option<String> {
+        self.sgr(38, 30)
+    }
+
+    /// Returns the ANSI escape sequence selecting this color as background.
+    ///
+    /// # Returns
+    /// - `Option<String>`: The escape sequence, or `None` for an unknown color name.
+    pub fn background_escape(&self) -> Option<String> {
+        self.sgr(48, 40)
+    }
+
+    /// Builds the escape sequence of this color from the SGR codes of its layer.
+    ///
+    /// # Parameters
+    /// - `extended`: The code selecting a 256-color index, 38 for foreground or 48 for background.
+    /// - `base`: The code of black in that layer, 30 for foreground or 40 for background.
+    fn sgr(&self, extended: u8, base: usize) -> Option<String> {
+        match self {
+            TextColor::Index(index) => Some(format!("\x1b[{extended};5;{index}m")),
+            TextColor::Name(name) => {
+                let name = name.to_lowercase();
+                let (color, offset) = match name.strip_prefix("bright_") {
+                    Some(color) => (color, base + 60),
+                    None => (name.as_str(), base),
+                };
+                COLOR_NAMES
+                    .iter()
+                    .position(|c| *c == color)
+                    .map(|i| format!("\x1b[{}m", offset + i))
             }
-            None => "shiny_log.json".to_string(), // Fallback if home directory cannot be determined
+        }
+    }
+}
+
+impl FromStr for TextColor {
+    type Err = String;
+
+    /// Parses a 256-color index or a color name, rejecting unknown names.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let color = match s.parse() {
+            Ok(index) => TextColor::Index(index),
+            Err(_) => TextColor::Name(s.to_string()),
         };
+        match color.escape() {
+            Some(_) => Ok(color),
+            None => Err(format!(
+                "should be a 256-color index or one of [{}], optionally prefixed with bright_",
+                COLOR_NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for TextColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TextColor::Index(index) => write!(f, "{index}"),
+            TextColor::Name(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+/// Returns the path of a data file (e.g. the shiny log) in the application's config
+/// directory, the given config directory joined with `kingler`, creating the directory.
+///
+/// Earlier versions kept these files in `~/.config/kingler` on every platform. Where the
+/// config directory is elsewhere (macOS, Windows, or a custom `XDG_CONFIG_HOME`), a file
+/// found there is moved to the new location unless one already exists.
+///
+/// # Parameters
+/// - `config_dir`: The platform's config directory, if it could be determined.
+/// - `file_name`: The name of the file.
+///
+/// # Returns
+/// - `Ok(PathBuf)`: The path of the file.
+/// - `Err(io::Error)`: An error if there is no config directory or the directory could not
+///   be created.
+fn data_file_path(config_dir: Option<PathBuf>, file_name: &str) -> io::Result<PathBuf> {
+    let Some(config_dir) = config_dir else {
+        return Err(io::Error::new(
+            NotFound,
+            "the config directory could not be determined",
+        ));
+    };
+    let dir = config_dir.join(BINARY_NAME);
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(file_name);
+
+    let legacy = legacy_data_file_path(file_name);
+    if let Some(legacy) = legacy.filter(|legacy| legacy.is_file() && *legacy != path) {
+        if !path.exists() {
+            // Renaming fails across file systems, so fall back to copying
+            match fs::rename(&legacy, &path)
+                .or_else(|_| fs::copy(&legacy, &path).and_then(|_| fs::remove_file(&legacy)))
+            {
+                Ok(()) => eprintln!("Moved {} to {}", legacy.display(), path.display()),
+                Err(e) => eprintln!("Failed to move {}: {}", legacy.display(), e),
+            }
+        }
+    }
+
+    Ok(path)
+}
+
+/// Returns the path of a data file like [`data_file_path`] does, falling back to the
+/// system's temporary directory when the config directory cannot be determined or
+/// created (e.g. a read-only home), so the program keeps working.
+///
+/// # Parameters
+/// - `file_name`: The name of the file.
+///
+/// # Returns
+/// - `PathBuf`: The path of the file.
+pub fn data_file_path_or_temp(file_name: &str) -> PathBuf {
+    data_file_path_in(dirs::config_dir(), file_name)
+}
+
+/// Returns the path of a data file in the given config directory, or in the temporary
+/// directory when that fails, see [`data_file_path_or_temp`].
+fn data_file_path_in(config_dir: Option<PathBuf>, file_name: &str) -> PathBuf {
+    data_file_path(config_dir, file_name).unwrap_or_else(|e| {
+        let dir = temp_data_dir();
+        // Any failure here surfaces once the file is written
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join(file_name);
+        eprintln!(
+            "Failed to create the kingler config directory ({e}), using {}",
+            path.display()
+        );
+        path
+    })
+}
+
+/// Returns the directory used in place of the config directory when it is unavailable.
+fn temp_data_dir() -> PathBuf {
+    env::temp_dir().join(BINARY_NAME)
+}
+
+/// Returns the path of the configuration file in the given config directory, falling back
+/// to the system's temporary directory, like the data files, when there is none.
+///
+/// # Parameters
+/// - `config_dir`: The platform's config directory, if it could be determined.
+///
+/// # Returns
+/// - `PathBuf`: The path of the configuration file.
+fn config_file_path(config_dir: Option<PathBuf>) -> PathBuf {
+    let dir = match config_dir {
+        // Join with the binary name to get the config path.
+        Some(dir) => dir.join(BINARY_NAME),
+        None => {
+            let dir = temp_data_dir();
+            eprintln!(
+                "Config directory could not be determined, using {}",
+                dir.display()
+            );
+            dir
+        }
+    };
+    dir.join("config.toml")
+}
+
+/// Returns where earlier versions kept a data file, `~/.config/kingler` on every platform.
+fn legacy_data_file_path(file_name: &str) -> Option<PathBuf> {
+    home_dir().map(|home| home.join(".config").join(BINARY_NAME).join(file_name))
+}
+
+/// Provides default values for the configuration settings.
+impl Default for Config {
+    fn default() -> Self {
+        // The shiny log lives next to the config file
+        let shiny_log_path = data_file_path_or_temp("shiny_log.json")
+            .to_string_lossy()
+            .into_owned();
 
         Self {
-            language: "en".to_string(), // Default language is English.
-            shiny_rate: 3.0 / 4096.0,   // Default shiny rate is 1 in 128.
-            shiny_log_path,             // Use the constructed path
+            language: "en".to_string(),    // Default language is English.
+            language_fallback: Vec::new(), // No fallback language.
+            shiny_rate: 3.0 / 4096.0,      // Default shiny rate is 1 in 128.
+            shiny_log_path,                // Use the constructed path
+            track_encounters: false,       // Encounters are only recorded with --track.
+            description_color: TextColor::Name("white".to_string()), // Descriptions are white.
+            assets_dir: None,              // Only the embedded assets are used.
+            weights: HashMap::new(),       // Every Pokémon is equally likely by default.
+            form_weights: HashMap::new(),  // Alternate forms are rarer than the regular one.
+            stat_tiers: StatTiers::default(), // Under 50 is low, above 90 is high.
+            show_dex: false,               // Only the name is shown.
+            default_info: false,           // Pokédex entries are only printed with --info.
+            default_shiny: false,          // Shiny art is only shown with --shiny or by chance.
+            default_under: false,          // Pokédex entries are beside the art.
+            link_names: false,             // Names are plain text.
+            link_url: "https://pokemondb.net/pokedex/{slug}".to_string(),
+            aliases: HashMap::new(), // No alias.
+            max_retries: 10,         // Enough unless filters leave few forms with art.
         }
     }
 }
 
 impl Config {
+    /// Returns the path of the configuration file.
+    ///
+    /// The `KINGLER_CONFIG` environment variable takes precedence when set and
+    /// not empty, otherwise `config.toml` in the application's config directory is used.
+    ///
+    /// # Returns
+    /// - `PathBuf`: The path of the configuration file.
+    pub fn path() -> PathBuf {
+        if let Some(path) = env::var_os(CONFIG_ENV_VAR).filter(|p| !p.is_empty()) {
+            return PathBuf::from(path);
+        }
+
+        config_file_path(dirs::config_dir())
+    }
+
+    /// Returns the languages names and descriptions are looked up in, in order.
+    ///
+    /// The languages are given as keys of the Pokémon database (e.g. `ja-Hrkt` for the
+    /// configured `ja_hrkt`), see [`Language::data_key`].
+    ///
+    /// # Returns
+    /// - `Vec<&str>`: The configured language followed by `language_fallback`.
+    pub fn languages(&self) -> Vec<&str> {
+        std::iter::once(&self.language)
+            .chain(&self.language_fallback)
+            .map(|code| Language::from_code(code).map_or(code.as_str(), |l| l.data_key()))
+            .collect()
+    }
+
+    /// Returns the weight of a Pokémon for random selection.
+    ///
+    /// A weight configured for the slug takes precedence over one configured
+    /// for the generation.
+    ///
+    /// # Parameters
+    /// - `pokemon`: The Pokémon to look up.
+    ///
+    /// # Returns
+    /// - `f64`: The configured weight, or 1 if there is none.
+    pub fn weight(&self, pokemon: &Pokemon) -> f64 {
+        self.weights
+            .get(&pokemon.slug)
+            .or_else(|| self.weights.get(&pokemon.gen.to_string()))
+            .copied()
+            .unwrap_or(1.0)
+    }
+
+    /// Returns the weight of a form for random selection.
+    ///
+    /// # Parameters
+    /// - `form`: The form, `regular` for the base form.
+    ///
+    /// # Returns
+    /// - `f64`: The configured weight, or 1 for the regular form and
+    ///   `DEFAULT_ALTERNATE_FORM_WEIGHT` for any other form if there is none.
+    pub fn form_weight(&self, form: &str) -> f64 {
+        match self.form_weights.get(form) {
+            Some(weight) => *weight,
+            None if form == "regular" => 1.0,
+            None => DEFAULT_ALTERNATE_FORM_WEIGHT,
+        }
+    }
+
     /// Loads the configuration from a `config.toml` file.
     ///
+    /// The file pointed to by `KINGLER_CONFIG` is used instead when that variable is set.
     /// If the configuration file does not exist, a default configuration file
     /// is created in the application's config directory.
     ///
+    /// Settings are then overridden by the `KINGLER_*` environment variables, so
+    /// the precedence is environment, then file, then defaults.
+    ///
     /// # Returns
     /// - `Ok(Config)`: The loaded configuration.
     /// - `Err(Error)`: An error if the configuration could not be loaded or created.
     pub fn load() -> Result<Self, Error> {
-        // Get the configuration directory path.
-        let config_dir = match dirs::config_dir() {
-            Some(dir) => dir.join(BINARY_NAME), // Join with the binary name to get the config path.
-            _none => {
-                return Err(Error::Configuration(
-                    "Failed to get config directory".to_string(),
-                ));
-            }
-        };
+        let mut config = Self::load_file()?;
+        config.apply_env_overrides()?;
+        config.validate()?;
+
+        // Fall back to white on an unknown description color instead of failing.
+        if config.description_color.escape().is_none() {
+            eprintln!(
+                "Invalid description_color `{}` in configuration file, using white",
+                config.description_color
+            );
+            config.description_color = TextColor::Name("white".to_string());
+        }
+
+        Ok(config) // Return the loaded or default configuration.
+    }
 
+    /// Reads the configuration file, creating it with the defaults when it does not
+    /// exist. Environment overrides are not applied.
+    ///
+    /// # Returns
+    /// - `Ok(Config)`: The configuration as stored in the file.
+    /// - `Err(Error)`: An error if the file could not be read or parsed.
+    pub fn load_file() -> Result<Self, Error> {
         // Define the path to the config file.
-        let config_file = config_dir.join("config.toml");
+        let config_file = Self::path();
+        let config_dir = config_file.parent().map(PathBuf::from).unwrap_or_default();
 
         // Try to read the config file.
         let config = match fs::read_to_string(&config_file) {
-            Ok(c) => {
-                // Parse the contents of the config file as TOML.
-                toml::from_str(&c).expect("Failed to parse TOML in configuration file")
-            }
+            // Parse the contents of the config file as TOML.
+            Ok(c) => toml::from_str(&c).map_err(|e| {
+                Error::Configuration(format!("Failed to parse {}: {}", config_file.display(), e))
+            })?,
 
-            // Handle case where the config file does not exist.
-            Err(ref e) if e.kind() == NotFound => {
+            // Handle case where the config file does not exist, nor maybe its directory.
+            Err(ref e) if matches!(e.kind(), NotFound | NotADirectory) => {
                 let config = Config::default(); // Create a default configuration.
-                let toml =
-                    toml::to_string_pretty(&config).expect("Failed to convert config to TOML");
+                let toml = toml::to_string_pretty(&config).map_err(|e| {
+                    Error::Configuration(format!("Failed to convert config to TOML: {e}"))
+                })?;
 
-                // Create the config directory if it does not exist.
-                fs::create_dir_all(config_dir).expect("Failed to create config directory");
-
-                // Write the default configuration to the config file.
-                fs::write(&config_file, toml).expect("Failed to write config file");
+                // Create the config directory if it does not exist, then write the default
+                // configuration. The defaults still apply when this fails (e.g. read-only home).
+                if let Err(e) =
+                    fs::create_dir_all(config_dir).and_then(|_| fs::write(&config_file, toml))
+                {
+                    eprintln!(
+                        "Failed to write the default configuration to {}: {}",
+                        config_file.display(),
+                        e
+                    );
+                }
                 config
             }
 
             // Handle any other errors encountered while reading the file.
-            Err(_) => {
-                return Err(Error::Configuration(
-                    "Failed to load configuration file".to_string(),
-                ));
+            Err(e) => {
+                return Err(Error::Configuration(format!(
+                    "Failed to load configuration file {}: {}",
+                    config_file.display(),
+                    e
+                )));
             }
         };
 
-        Ok(config) // Return the loaded or default configuration.
+        let mut config: Config = config;
+        // Config files written by earlier versions pin the old default shiny log location
+        if legacy_data_file_path("shiny_log.json")
+            .is_some_and(|legacy| legacy == Path::new(&config.shiny_log_path))
+        {
+            config.shiny_log_path = Config::default().shiny_log_path;
+        }
+        Ok(config)
+    }
+
+    /// Checks that the settings hold valid values.
+    ///
+    /// # Returns
+    /// - `Ok(())`: The settings are valid.
+    /// - `Err(Error)`: An error describing the first invalid setting.
+    fn validate(&self) -> Result<(), Error> {
+        Language::from_code(&self.language)?;
+        for language in &self.language_fallback {
+            Language::from_code(language).map_err(|_| {
+                Error::Configuration(format!(
+                    "language_fallback `{language}` should be one of [{}]",
+                    Language::supported_codes()
+                ))
+            })?;
+        }
+
+        if !(0.0..=1.0).contains(&self.shiny_rate) {
+            return Err(Error::Configuration(format!(
+                "shiny_rate `{}` should be a number between 0.0 and 1.0",
+                self.shiny_rate
+            )));
+        }
+
+        if self.max_retries == 0 {
+            return Err(Error::Configuration(
+                "max_retries should be at least 1".to_string(),
+            ));
+        }
+
+        if self.stat_tiers.low > self.stat_tiers.high {
+            return Err(Error::Configuration(format!(
+                "stat_tiers low `{}` should not be greater than high `{}`",
+                self.stat_tiers.low, self.stat_tiers.high
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Warns about the aliases that cannot be used: those whose Pokémon is not in the
+    /// database, and those named like an actual Pokémon, which takes precedence.
+    ///
+    /// # Parameters
+    /// - `pokemon_db`: The Pokémon database the aliases point into.
+    pub fn check_aliases(&self, pokemon_db: &PokemonDb) {
+        let mut names: Vec<&String> = self.aliases.keys().collect();
+        names.sort();
+
+        for name in names {
+            let alias = &self.aliases[name];
+            if pokemon_db.get(&name.to_lowercase()).is_some() {
+                eprintln!("Ignoring the alias `{name}`, a Pokémon has that name");
+            } else if pokemon_db.get(&alias.slug).is_none() {
+                eprintln!(
+                    "The alias `{name}` points to `{}`, which is not a known Pokémon",
+                    alias.slug
+                );
+            }
+        }
+    }
+
+    /// Overrides settings with the `KINGLER_LANGUAGE`, `KINGLER_SHINY_RATE` and
+    /// `KINGLER_SHINY_LOG_PATH` environment variables, when set and not empty.
+    ///
+    /// # Returns
+    /// - `Ok(())`: The overrides were applied.
+    /// - `Err(Error)`: An error if `KINGLER_SHINY_RATE` is not a number.
+    fn apply_env_overrides(&mut self) -> Result<(), Error> {
+        let var = |name: &str| env::var(name).ok().filter(|v| !v.is_empty());
+
+        if let Some(language) = var("KINGLER_LANGUAGE") {
+            self.language = language;
+        }
+        if let Some(shiny_rate) = var("KINGLER_SHINY_RATE") {
+            self.shiny_rate = shiny_rate.parse().map_err(|_| {
+                Error::Configuration(format!(
+                    "KINGLER_SHINY_RATE `{}` should be a number between 0.0 and 1.0",
+                    shiny_rate
+                ))
+            })?;
+        }
+        if let Some(shiny_log_path) = var("KINGLER_SHINY_LOG_PATH") {
+            self.shiny_log_path = shiny_log_path;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the value of a setting, as it would be written in the configuration file.
+    ///
+    /// # Parameters
+    /// - `key`: The setting, one of `language`, `shiny_rate` or `shiny_log_path`.
+    ///
+    /// # Returns
+    /// - `Ok(String)`: The value of the setting.
+    /// - `Err(Error)`: A configuration error if the key is unknown.
+    pub fn get(&self, key: &str) -> Result<String, Error> {
+        match key {
+            "language" => Ok(self.language.clone()),
+            "shiny_rate" => Ok(self.shiny_rate.to_string()),
+            "shiny_log_path" => Ok(self.shiny_log_path.clone()),
+            _ => Err(unknown_key(key)),
+        }
+    }
+
+    /// Changes a setting, checking the new value like [`Config::load`] does.
+    ///
+    /// # Parameters
+    /// - `key`: The setting, one of `language`, `shiny_rate` or `shiny_log_path`.
+    /// - `value`: The new value of the setting.
+    ///
+    /// # Returns
+    /// - `Ok(())`: The setting was changed.
+    /// - `Err(Error)`: A configuration error if the key is unknown or the value invalid,
+    ///   in which case the configuration is left untouched.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), Error> {
+        let mut config = self.clone();
+        match key {
+            "language" => config.language = value.to_string(),
+            "shiny_rate" => {
+                config.shiny_rate = value.parse().map_err(|_| {
+                    Error::Configuration(format!(
+                        "shiny_rate `{}` should be a number between 0.0 and 1.0",
+                        value
+                    ))
+                })?
+            }
+            "shiny_log_path" => config.shiny_log_path = value.to_string(),
+            _ => return Err(unknown_key(key)),
+        }
+        config.validate().map_err(|e| match e {
+            Error::Configuration(_) => e,
+            e => Error::Configuration(e.to_string()),
+        })?;
+
+        *self = config;
+        Ok(())
+    }
+
+    /// Writes the configuration to the configuration file, replacing its contents.
+    ///
+    /// # Returns
+    /// - `Ok(())`: The configuration was written.
+    /// - `Err(Error)`: An error if the configuration could not be serialized or written.
+    pub fn save(&self) -> Result<(), Error> {
+        let config_file = Self::path();
+        let toml = toml::to_string_pretty(self)
+            .map_err(|e| Error::Configuration(format!("Failed to convert config to TOML: {e}")))?;
+
+        if let Some(config_dir) = config_file.parent() {
+            fs::create_dir_all(config_dir)?;
+        }
+        fs::write(&config_file, toml).map_err(|e| {
+            Error::Configuration(format!("Failed to write {}: {}", config_file.display(), e))
+        })
+    }
+}
+
+/// Returns the error for a setting that `config get` and `config set` do not know.
+fn unknown_key(key: &str) -> Error {
+    Error::Configuration(format!(
+        "Unknown setting `{}`, should be one of [language, shiny_rate, shiny_log_path]",
+        key
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_file_path_falls_back_to_temp_dir_without_config_dir() {
+        let file_name = format!("test-{}-missing.json", std::process::id());
+
+        assert_eq!(
+            data_file_path_in(None, &file_name),
+            env::temp_dir().join(BINARY_NAME).join(&file_name)
+        );
+    }
+
+    #[test]
+    fn data_file_path_falls_back_to_temp_dir_when_unwritable() {
+        // A file where the config directory should be makes creating it fail
+        let base = env::temp_dir().join(format!("kingler-{}-not-a-dir", std::process::id()));
+        fs::write(&base, "").unwrap();
+        let file_name = format!("test-{}-unwritable.json", std::process::id());
+
+        assert!(data_file_path(Some(base.clone()), &file_name).is_err());
+        assert_eq!(
+            data_file_path_in(Some(base.clone()), &file_name),
+            env::temp_dir().join(BINARY_NAME).join(&file_name)
+        );
+        fs::remove_file(base).unwrap();
+    }
+
+    #[test]
+    fn data_file_path_creates_the_config_dir() {
+        let base = env::temp_dir().join(format!("kingler-{}-config", std::process::id()));
+        let file_name = format!("test-{}-created.json", std::process::id());
+
+        assert_eq!(
+            data_file_path_in(Some(base.clone()), &file_name),
+            base.join(BINARY_NAME).join(&file_name)
+        );
+        assert!(base.join(BINARY_NAME).is_dir());
+        fs::remove_dir_all(base).unwrap();
+    }
+
+    #[test]
+    fn language_round_trips_and_looks_up_data_keys() {
+        let mut config = Config::default();
+        config.set("language", "ja_hrkt").unwrap();
+        config.language_fallback = vec!["zh-hans".to_string(), "en".to_string()];
+
+        assert_eq!(config.get("language").unwrap(), "ja_hrkt");
+        assert_eq!(config.languages(), ["ja-Hrkt", "zh-Hans", "en"]);
+    }
+
+    #[test]
+    fn config_file_path_falls_back_to_temp_dir() {
+        assert_eq!(
+            config_file_path(None),
+            env::temp_dir().join(BINARY_NAME).join("config.toml")
+        );
+        assert_eq!(
+            config_file_path(Some(PathBuf::from("/etc/xdg"))),
+            Path::new("/etc/xdg/kingler/config.toml")
+        );
     }
 }
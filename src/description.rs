@@ -1,6 +1,8 @@
+use crate::error::Error;
 use crate::Config;
 use crate::Pokemon;
 use rand::prelude::IndexedRandom;
+use rand::Rng;
 
 /// Retrieves a random description for a given Pokémon based on the configured language.
 ///
@@ -10,23 +12,246 @@ use rand::prelude::IndexedRandom;
 /// # Parameters
 /// - `pokemon`: A reference to a `Pokemon` instance containing possible descriptions.
 /// - `config`: A reference to a `Config` instance that includes the preferred language.
+/// - `rng`: The random number generator picking the game.
 ///
 /// # Returns
-/// - `Vec<&str>`: A vector containing the lines of the selected description if found,
-///   or an empty vector if no descriptions are available in the specified language.
-pub fn get_random_description<'a>(pokemon: &'a Pokemon, config: &'a Config) -> Vec<&'a str> {
+/// - `Option<(&str, Vec<&str>)>`: The game of the selected description and its lines if
+///   found, or `None` if no descriptions are available in the specified language.
+pub fn get_random_description<'a>(
+    pokemon: &'a Pokemon,
+    config: &'a Config,
+    rng: &mut impl Rng,
+) -> Option<(&'a str, Vec<&'a str>)> {
     // Attempt to get the descriptions for the specified language from the Pokémon.
-    if let Some(descriptions) = pokemon.desc.get(&config.language) {
-        // Collect the keys (game names) from the descriptions.
-        let game_keys: Vec<&String> = descriptions.keys().collect();
+    if let Some(descriptions) = pokemon.desc.get(description_language(pokemon, config)) {
+        // Collect the keys (game names) from the descriptions, sorted so a seeded
+        // generator always picks the same game.
+        let mut game_keys: Vec<&String> = descriptions.keys().collect();
+        game_keys.sort();
 
         // Randomly choose a game from the available keys.
-        if let Some(random_game) = game_keys.choose(&mut rand::rng()) {
+        if let Some(random_game) = game_keys.choose(rng) {
             // Retrieve and return the lines of the chosen description.
             if let Some(desc) = descriptions.get(*random_game) {
-                return desc.lines().collect(); // Return lines from the selected description.
+                return Some((random_game, desc.lines().collect())); // Return lines from the selected description.
             }
         }
     }
-    Vec::new() // Return an empty vector if no descriptions are found.
+    None // No descriptions were found.
+}
+
+/// Retrieves the description at a given position for a Pokémon based on the configured language.
+///
+/// The available descriptions are sorted by game name so the same index always
+/// yields the same description. Indices past the end wrap around.
+///
+/// # Parameters
+/// - `pokemon`: A reference to a `Pokemon` instance containing possible descriptions.
+/// - `config`: A reference to a `Config` instance that includes the preferred language.
+/// - `index`: The position of the description among the games sorted by name.
+///
+/// # Returns
+/// - `Option<(&str, Vec<&str>)>`: The game of the selected description and its lines if
+///   found, or `None` if no descriptions are available in the specified language.
+pub fn get_description_by_index<'a>(
+    pokemon: &'a Pokemon,
+    config: &'a Config,
+    index: usize,
+) -> Option<(&'a str, Vec<&'a str>)> {
+    if let Some(descriptions) = pokemon.desc.get(description_language(pokemon, config)) {
+        // Sort the game names so the index is stable across runs.
+        let mut game_keys: Vec<&String> = descriptions.keys().collect();
+        game_keys.sort();
+
+        if !game_keys.is_empty() {
+            let game = game_keys[index % game_keys.len()];
+            return Some((game, descriptions[game].lines().collect()));
+        }
+    }
+    None // No descriptions were found.
+}
+
+/// Selects the description of a Pokémon shown with `--info` and by the describe command.
+///
+/// The description of `game_info` is used when given and available, otherwise the one
+/// at `desc_index`, otherwise a random one. The game is looked up with [`find_game`].
+/// Descriptions come from the configured language, or else from the first language of
+/// `language_fallback` that has some.
+///
+/// # Parameters
+/// - `pokemon`: A reference to a `Pokemon` instance containing possible descriptions.
+/// - `config`: A reference to a `Config` instance that includes the preferred language.
+/// - `game_info`: The game whose description is wanted, or part of its name, empty for any game.
+/// - `desc_index`: The position of the description among the games sorted by name, if any.
+/// - `rng`: The random number generator picking the game when none is chosen.
+///
+/// # Returns
+/// - `Ok(Some((&str, Vec<&str>)))`: The game of the selected description and its lines.
+/// - `Ok(None)`: No descriptions are available in the specified language.
+/// - `Err(Error)`: `Error::AmbiguousGame` if `game_info` matches several games.
+pub fn select_description<'a>(
+    pokemon: &'a Pokemon,
+    config: &'a Config,
+    game_info: &str,
+    desc_index: Option<usize>,
+    rng: &mut impl Rng,
+) -> Result<Option<(&'a str, Vec<&'a str>)>, Error> {
+    if !game_info.is_empty() {
+        // An unknown game falls back to a random description
+        let language = description_language(pokemon, config);
+        return Ok(match find_game(pokemon, language, game_info)? {
+            Some(game) => Some((game, pokemon.desc[language][game].lines().collect())),
+            None => get_random_description(pokemon, config, rng),
+        });
+    }
+
+    Ok(match desc_index {
+        Some(index) => get_description_by_index(pokemon, config, index),
+        None => get_random_description(pokemon, config, rng),
+    })
+}
+
+/// Returns the language the descriptions of a Pokémon are shown in: the configured
+/// language, or else the first fallback language with descriptions for it.
+///
+/// # Parameters
+/// - `pokemon`: A reference to a `Pokemon` instance containing possible descriptions.
+/// - `config`: A reference to a `Config` instance that includes the languages.
+///
+/// # Returns
+/// - `&str`: The first language with a description, or the configured one if there is none.
+fn description_language<'a>(pokemon: &Pokemon, config: &'a Config) -> &'a str {
+    let languages = config.languages();
+    languages
+        .iter()
+        .copied()
+        .find(|language| {
+            pokemon
+                .desc
+                .get(*language)
+                .is_some_and(|games| !games.is_empty())
+        })
+        .unwrap_or(languages[0])
+}
+
+/// Returns the games a Pokémon has a description for in a language, sorted by name.
+///
+/// # Parameters
+/// - `pokemon`: A reference to a `Pokemon` instance containing possible descriptions.
+/// - `language`: The language code of the descriptions.
+///
+/// # Returns
+/// - `Vec<&str>`: The game keys, e.g. `omega-ruby`, empty if there is no description.
+pub fn available_games<'a>(pokemon: &'a Pokemon, language: &str) -> Vec<&'a str> {
+    let mut games: Vec<&str> = pokemon
+        .desc
+        .get(language)
+        .map(|games| games.keys().map(String::as_str).collect())
+        .unwrap_or_default();
+    games.sort();
+    games
+}
+
+/// Finds the game a possibly abbreviated game name stands for, ignoring case.
+///
+/// An exact match wins, then a single game starting with the name (`omega` for
+/// `omega-ruby`), then a single game containing it (`ruby` would match both
+/// `ruby` and `omega-ruby`, but the exact match wins).
+///
+/// # Parameters
+/// - `pokemon`: A reference to a `Pokemon` instance containing possible descriptions.
+/// - `language`: The language code of the descriptions.
+/// - `game_info`: The game name as typed by the user.
+///
+/// # Returns
+/// - `Ok(Some(&str))`: The game key the name stands for.
+/// - `Ok(None)`: No game matches the name.
+/// - `Err(Error)`: `Error::AmbiguousGame` listing the candidates when several games match.
+pub fn find_game<'a>(
+    pokemon: &'a Pokemon,
+    language: &str,
+    game_info: &str,
+) -> Result<Option<&'a str>, Error> {
+    let games = available_games(pokemon, language);
+    let wanted = game_info.to_lowercase();
+
+    if let Some(game) = games.iter().find(|game| game.to_lowercase() == wanted) {
+        return Ok(Some(game));
+    }
+    let prefixed: Vec<&str> = games
+        .iter()
+        .copied()
+        .filter(|game| game.to_lowercase().starts_with(&wanted))
+        .collect();
+    let candidates = if prefixed.is_empty() {
+        games
+            .into_iter()
+            .filter(|game| game.to_lowercase().contains(&wanted))
+            .collect()
+    } else {
+        prefixed
+    };
+
+    match candidates.as_slice() {
+        [] => Ok(None),
+        [game] => Ok(Some(game)),
+        _ => Err(Error::AmbiguousGame(
+            game_info.to_string(),
+            candidates.join(", "),
+        )),
+    }
+}
+
+/// Formats a game key as the title shown after a description, e.g. `Omega Ruby` for `omega-ruby`.
+///
+/// # Parameters
+/// - `game`: The game key of the description.
+///
+/// # Returns
+/// - `String`: The words of the key, capitalized and separated by spaces.
+pub fn game_title(game: &str) -> String {
+    game.split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Builds the notice shown when a Pokémon has no description in the configured language.
+///
+/// The notice lists the languages that do have descriptions for the Pokémon,
+/// so the user knows what to set in the configuration.
+///
+/// # Parameters
+/// - `pokemon`: A reference to a `Pokemon` instance containing possible descriptions.
+/// - `config`: A reference to a `Config` instance that includes the preferred language.
+///
+/// # Returns
+/// - `String`: The notice to display.
+pub fn missing_notice(pokemon: &Pokemon, config: &Config) -> String {
+    let mut languages: Vec<&str> = pokemon
+        .desc
+        .iter()
+        .filter(|(_, games)| !games.is_empty())
+        .map(|(language, _)| language.as_str())
+        .collect();
+    languages.sort();
+
+    if languages.is_empty() {
+        format!(
+            "No descriptions available for language: {} (none in any language)",
+            config.language
+        )
+    } else {
+        format!(
+            "No descriptions available for language: {} (available in: {})",
+            config.language,
+            languages.join(", ")
+        )
+    }
 }
@@ -1,7 +1,16 @@
 use std::io;
 use thiserror::Error;
 
+use crate::language::Language;
+
 /// An enumeration representing errors that can occur in the application.
+///
+/// Each error category exits the process with its own code, see [`Error::exit_code`]:
+/// - `2`: invalid input (`InvalidPokemon`, `InvalidForm`, `InvalidGeneration`, `InvalidDexRange`,
+///   `InvalidShinyChance`, `InvalidStat`, `AmbiguousGame`, `TooManyAttempts`).
+/// - `3`: configuration problems (`Configuration`, `InvalidLanguage`).
+/// - `4`: I/O and data loading failures (`Io`, `PokemonDb`, `DataIntegrity`, `MissingData`,
+///   `MissingName`).
 #[derive(Error, Debug)]
 pub enum Error {
     /// Represents a configuration error with a descriptive message.
@@ -16,21 +25,104 @@ pub enum Error {
     #[error("Invalid pokemon `{0}`")]
     InvalidPokemon(String),
 
+    /// Signifies that a Pokémon has no such form, with the forms it has and the closest one.
+    #[error("Invalid form `{1}` for `{0}`, {2}")]
+    InvalidForm(String, String, String),
+
     /// Represents an error due to an invalid language code.
-    #[error("Invalid language `{0}`, should be one of [en, fr, de, it, es, ko, ja, ja_hrkt, zh_hans, zh_hant]")]
+    #[error(
+        "Invalid language `{0}`, should be one of [{codes}]",
+        codes = Language::supported_codes()
+    )]
     InvalidLanguage(String),
 
-    /// Indicates that the specified generations are invalid.
-    #[error("Invalid generations `{0}`, should be integers between 1 and 9")]
-    InvalidGeneration(String),
+    /// Indicates that the specified generations are invalid, with the reason why.
+    #[error("Invalid generations `{0}`, {1}")]
+    InvalidGeneration(String, String),
+
+    /// Indicates that the National Dex range is invalid, with the reason why.
+    #[error("Invalid dex range `{0}`, {1}")]
+    InvalidDexRange(String, String),
+
+    /// Indicates that the shiny chance is not a probability.
+    #[error("Invalid shiny chance `{0}`, should be a number between 0.0 and 1.0")]
+    InvalidShinyChance(f64),
+
+    /// Indicates that the stat name is not one of the known stats.
+    #[error("Invalid stat `{0}`, should be one of [hp, attack, defense, special-attack, special-defense, speed, total]")]
+    InvalidStat(String),
+
+    /// Indicates that a game name given with `--game-info` matches several games, listed second.
+    #[error("Ambiguous game `{0}`, it matches [{1}]")]
+    AmbiguousGame(String, String),
+
+    /// Indicates that no picked Pokémon could be shown, with the number of attempts
+    /// and the filters that were active.
+    #[error("No Pokémon could be shown after {0} attempts, with [{1}]; try a larger --max-retries or fewer filters")]
+    TooManyAttempts(usize, String),
+
+    /// Indicates that the doctor command found problems in the Pokémon data, with their number.
+    #[error("Found {0} problem(s) in the Pokémon data")]
+    DataIntegrity(usize),
+
+    /// Indicates that the Pokémon data lacks what a command needs, naming the missing field.
+    #[error("The Pokémon data has no {0}, regenerate it with utility/pokemon_scraping.py")]
+    MissingData(String),
+
+    /// Indicates that a Pokémon has no name in any of the languages tried, listed second.
+    #[error("No name for `{0}` in [{1}], add a language it has to language_fallback")]
+    MissingName(String, String),
 
     /// Indicates an IO error occurred.
     #[error("I/O error: {0}")]
-    IoError(String),
+    Io(String),
 }
 
 impl From<io::Error> for Error {
     fn from(error: io::Error) -> Self {
-        Error::IoError(error.to_string()) // Convert io::Error to String
+        Error::Io(error.to_string()) // Convert io::Error to String
+    }
+}
+
+impl Error {
+    /// Returns the process exit code for this error, grouped by category.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Error::InvalidPokemon(_)
+            | Error::InvalidForm(..)
+            | Error::InvalidGeneration(..)
+            | Error::InvalidDexRange(..)
+            | Error::InvalidShinyChance(_)
+            | Error::InvalidStat(_)
+            | Error::AmbiguousGame(..)
+            | Error::TooManyAttempts(..) => 2,
+            Error::Configuration(_) | Error::InvalidLanguage(_) => 3,
+            Error::Io(_)
+            | Error::PokemonDb(_)
+            | Error::DataIntegrity(_)
+            | Error::MissingData(_)
+            | Error::MissingName(..) => 4,
+        }
+    }
+
+    /// Returns a stable tag naming the kind of this error, as printed in JSON error reports.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::Configuration(_) => "Configuration",
+            Error::PokemonDb(_) => "PokemonDb",
+            Error::InvalidPokemon(_) => "InvalidPokemon",
+            Error::InvalidForm(..) => "InvalidForm",
+            Error::InvalidLanguage(_) => "InvalidLanguage",
+            Error::InvalidGeneration(..) => "InvalidGeneration",
+            Error::InvalidDexRange(..) => "InvalidDexRange",
+            Error::InvalidShinyChance(_) => "InvalidShinyChance",
+            Error::InvalidStat(_) => "InvalidStat",
+            Error::AmbiguousGame(..) => "AmbiguousGame",
+            Error::TooManyAttempts(..) => "TooManyAttempts",
+            Error::DataIntegrity(_) => "DataIntegrity",
+            Error::MissingData(_) => "MissingData",
+            Error::MissingName(..) => "MissingName",
+            Error::Io(_) => "Io",
+        }
     }
 }
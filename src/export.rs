@@ -0,0 +1,195 @@
+use clap::ValueEnum;
+use serde::Serialize;
+
+use std::io::{self, Write};
+
+use crate::error::Error;
+use crate::shiny_hunting::ShinyLogEntry;
+use crate::Pokemon;
+
+/// The stats exported for every Pokémon, in column order.
+const STAT_NAMES: [&str; 6] = [
+    "hp",
+    "attack",
+    "defense",
+    "special-attack",
+    "special-defense",
+    "speed",
+];
+
+/// Represents the output formats supported by the export command.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+/// Represents the output formats supported by the shiny log export.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum LogFormat {
+    Csv,
+    Md,
+}
+
+/// Represents a single exported Pokémon.
+///
+/// # Fields
+/// - `slug`: The unique identifier of the Pokémon.
+/// - `gen`: The generation of the Pokémon.
+/// - `name`: The English name of the Pokémon, if available.
+/// - `stats`: The six base stats of the regular form in `STAT_NAMES` order, `None` when missing.
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct Record<'a> {
+    slug: &'a str,
+    gen: u8,
+    name: Option<&'a str>,
+    hp: Option<u32>,
+    attack: Option<u32>,
+    defense: Option<u32>,
+    special_attack: Option<u32>,
+    special_defense: Option<u32>,
+    speed: Option<u32>,
+}
+
+impl<'a> Record<'a> {
+    fn new(pokemon: &'a Pokemon) -> Self {
+        let stat = |name: &str| {
+            pokemon
+                .stats_for("regular")
+                .and_then(|s| s.get(name).copied())
+        };
+
+        Self {
+            slug: &pokemon.slug,
+            gen: pokemon.gen,
+            name: pokemon.localized_name(&["en"]).ok(),
+            hp: stat("hp"),
+            attack: stat("attack"),
+            defense: stat("defense"),
+            special_attack: stat("special-attack"),
+            special_defense: stat("special-defense"),
+            speed: stat("speed"),
+        }
+    }
+
+    fn stats(&self) -> [Option<u32>; 6] {
+        [
+            self.hp,
+            self.attack,
+            self.defense,
+            self.special_attack,
+            self.special_defense,
+            self.speed,
+        ]
+    }
+}
+
+/// Writes the whole Pokémon database to standard output in the given format.
+///
+/// Pokémon without stats are still exported, with empty cells in CSV and
+/// `null` values in JSON.
+///
+/// # Parameters
+/// - `pokemon_db`: A vector of `Pokemon` objects to export.
+/// - `format`: The output format.
+///
+/// # Returns
+/// - `Result<(), Error>`: Returns `Ok(())` on success, or an `Error` if writing fails.
+pub fn export_pokemon(pokemon_db: Vec<Pokemon>, format: Format) -> Result<(), Error> {
+    let records: Vec<Record> = pokemon_db.iter().map(Record::new).collect();
+    let mut out = io::stdout().lock();
+
+    match format {
+        Format::Csv => {
+            writeln!(out, "slug,gen,name,{}", STAT_NAMES.join(","))?;
+            for record in &records {
+                let stats: Vec<String> = record
+                    .stats()
+                    .iter()
+                    .map(|s| s.map(|v| v.to_string()).unwrap_or_default())
+                    .collect();
+                writeln!(
+                    out,
+                    "{},{},{},{}",
+                    csv_field(record.slug),
+                    record.gen,
+                    csv_field(record.name.unwrap_or_default()),
+                    stats.join(",")
+                )?;
+            }
+        }
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut out, &records)
+                .map_err(|e| Error::Io(e.to_string()))?;
+            writeln!(out)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the shiny log to standard output as CSV or as a Markdown table.
+///
+/// The columns are the date, name, form, encounters and details of every capture,
+/// the encounters being empty for captures logged without them. An empty log is
+/// written as the header alone.
+///
+/// # Parameters
+/// - `entries`: The captures, in log order.
+/// - `format`: The output format.
+///
+/// # Returns
+/// - `Result<(), Error>`: Returns `Ok(())` on success, or an `Error` if writing fails.
+pub fn export_shiny_log(entries: &[ShinyLogEntry], format: LogFormat) -> Result<(), Error> {
+    const COLUMNS: [&str; 5] = ["date", "name", "form", "encounters", "details"];
+
+    let mut out = io::stdout().lock();
+    match format {
+        LogFormat::Csv => writeln!(out, "{}", COLUMNS.join(","))?,
+        LogFormat::Md => {
+            writeln!(out, "| {} |", COLUMNS.join(" | "))?;
+            writeln!(out, "|{}", "---|".repeat(COLUMNS.len()))?;
+        }
+    }
+
+    for entry in entries {
+        let encounters = entry.encounters.map(|n| n.to_string()).unwrap_or_default();
+        let fields = [
+            entry.date.as_str(),
+            &entry.pokemon_name,
+            &entry.form,
+            &encounters,
+            &entry.details,
+        ];
+        match format {
+            LogFormat::Csv => {
+                let fields: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+                writeln!(out, "{}", fields.join(","))?;
+            }
+            LogFormat::Md => {
+                let fields: Vec<String> = fields.iter().map(|f| markdown_cell(f)).collect();
+                writeln!(out, "| {} |", fields.join(" | "))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Escapes the pipes of a Markdown table cell and puts it on a single line.
+fn markdown_cell(value: &str) -> String {
+    value
+        .replace('|', "\\|")
+        .replace("\r\n", " ")
+        .replace(['\n', '\r'], " ")
+}
+
+/// Quotes a CSV field if it contains a separator, a quote or a line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,82 @@
+/// Common spellings of forms mapped to the form slug used by the colorscripts.
+const FORM_ALIASES: [(&str, &str); 5] = [
+    ("alolan", "alola"),
+    ("galarian", "galar"),
+    ("hisuian", "hisui"),
+    ("paldean", "paldea"),
+    ("gigantamax", "gmax"),
+];
+
+/// Normalizes a form as typed by the user to its canonical slug.
+///
+/// Adjective spellings such as `alolan` or `galarian` are mapped to the form
+/// slugs used in the colorscripts (`alola`, `galar`); any other form is
+/// returned unchanged.
+///
+/// # Parameters
+/// - `form`: The form as provided on the command line.
+///
+/// # Returns
+/// - `&str`: The canonical form slug.
+pub fn canonical_form(form: &str) -> &str {
+    FORM_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == form)
+        .map(|(_, canonical)| *canonical)
+        .unwrap_or(form)
+}
+
+/// The slugs of the regional forms.
+const REGIONAL_FORMS: [&str; 4] = ["alola", "galar", "hisui", "paldea"];
+
+/// Returns whether a form is a Mega Evolution, e.g. `mega` or `mega-x`.
+pub fn is_mega(form: &str) -> bool {
+    form == "mega" || form.starts_with("mega-")
+}
+
+/// Returns whether a form is a Gigantamax form.
+pub fn is_gmax(form: &str) -> bool {
+    form == "gmax"
+}
+
+/// Returns whether a form is a regional form, e.g. `alola` or `galar-zen`.
+pub fn is_regional(form: &str) -> bool {
+    REGIONAL_FORMS
+        .iter()
+        .any(|region| form == *region || form.starts_with(&format!("{region}-")))
+}
+
+/// Returns the form closest in spelling to a form that does not exist, to suggest it.
+///
+/// # Parameters
+/// - `form`: The form as provided on the command line.
+/// - `forms`: The forms the Pokémon actually has.
+///
+/// # Returns
+/// - `Option<&str>`: The form with the fewest edits away from `form`, or `None` if
+///   every form takes more than a third of its length (and at least 2) edits.
+pub fn closest_form<'a>(form: &str, forms: &'a [String]) -> Option<&'a str> {
+    let max_distance = (form.chars().count() / 3).max(2);
+    forms
+        .iter()
+        .map(|candidate| (edit_distance(form, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.as_str())
+}
+
+/// Returns the Levenshtein distance between two strings: the number of characters to
+/// insert, delete or replace to turn one into the other.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let replace = previous[j] + usize::from(a_char != *b_char);
+            current.push(replace.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
@@ -0,0 +1,102 @@
+use crate::error::Error;
+
+/// Represents the languages Pokémon names and descriptions can be printed in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Language {
+    En,
+    Fr,
+    De,
+    It,
+    Es,
+    Ko,
+    Ja,
+    JaHrkt,
+    ZhHans,
+    ZhHant,
+}
+
+/// Every supported language with its code, in the order they are listed to the user.
+const LANGUAGES: [(Language, &str); 10] = [
+    (Language::En, "en"),
+    (Language::Fr, "fr"),
+    (Language::De, "de"),
+    (Language::It, "it"),
+    (Language::Es, "es"),
+    (Language::Ko, "ko"),
+    (Language::Ja, "ja"),
+    (Language::JaHrkt, "ja_hrkt"),
+    (Language::ZhHans, "zh_hans"),
+    (Language::ZhHant, "zh_hant"),
+];
+
+impl Language {
+    /// Parses a language code.
+    ///
+    /// Codes are matched ignoring case, and `-` is accepted in place of `_`
+    /// (e.g. `ja-Hrkt` as in the Pokémon database).
+    ///
+    /// # Parameters
+    /// - `code`: The language code, e.g. `en` or `zh_hans`.
+    ///
+    /// # Returns
+    /// - `Ok(Language)`: The language for this code.
+    /// - `Err(Error)`: An `InvalidLanguage` error if the code is not supported.
+    pub fn from_code(code: &str) -> Result<Self, Error> {
+        let normalized = code.to_lowercase().replace('-', "_");
+        LANGUAGES
+            .iter()
+            .find(|(_, c)| *c == normalized)
+            .map(|(language, _)| *language)
+            .ok_or_else(|| Error::InvalidLanguage(code.to_string()))
+    }
+
+    /// Returns the key of this language in the Pokémon database, e.g. `ja-Hrkt` for `ja_hrkt`.
+    pub fn data_key(self) -> &'static str {
+        match self {
+            Language::En => "en",
+            Language::Fr => "fr",
+            Language::De => "de",
+            Language::It => "it",
+            Language::Es => "es",
+            Language::Ko => "ko",
+            Language::Ja => "ja",
+            Language::JaHrkt => "ja-Hrkt",
+            Language::ZhHans => "zh-Hans",
+            Language::ZhHant => "zh-Hant",
+        }
+    }
+
+    /// Returns the supported language codes as a comma-separated list, e.g. `en, fr, de`.
+    pub fn supported_codes() -> String {
+        LANGUAGES.map(|(_, code)| code).join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_keys_match_the_database() {
+        assert_eq!(
+            Language::from_code("ja_hrkt").unwrap().data_key(),
+            "ja-Hrkt"
+        );
+        assert_eq!(
+            Language::from_code("zh-hans").unwrap().data_key(),
+            "zh-Hans"
+        );
+        assert_eq!(
+            Language::from_code("ZH_HANT").unwrap().data_key(),
+            "zh-Hant"
+        );
+        assert_eq!(Language::from_code("en").unwrap().data_key(), "en");
+    }
+
+    #[test]
+    fn data_keys_parse_back() {
+        for (language, _) in LANGUAGES {
+            assert_eq!(Language::from_code(language.data_key()).unwrap(), language);
+        }
+    }
+}
@@ -1,29 +1,43 @@
 mod ascii;
+mod assets;
 mod cli;
 mod config;
 mod description;
 mod error;
+mod export;
+mod forms;
+mod language;
 mod pokemon;
 mod shiny_hunting;
 mod stats;
+mod types;
 
+use assets::Assets;
 use config::Config;
 use error::Error;
+use language::Language;
 use pokemon::*;
 
-use clap::Parser;
+use chrono::{Datelike, Local, NaiveDate, NaiveDateTime, Timelike};
+use clap::{Parser, ValueEnum};
 use clap_complete::Shell;
 use rand::prelude::IndexedRandom;
-use rand::Rng;
-use rust_embed::RustEmbed;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use serde::Deserialize;
 use serde::Serialize;
 
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::env;
 use std::fs;
 use std::io;
-use std::io::Write;
-use std::path::PathBuf;
+use std::io::{IsTerminal, Write};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode, Stdio};
 use std::str;
+use std::time::Duration;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct EncounteredPokemon {
@@ -35,19 +49,335 @@ struct EncounteredPokemonTracker {
     encounters: Vec<EncounteredPokemon>,
 }
 
-#[derive(RustEmbed)]
-#[folder = "assets/"]
-struct Asset;
+/// The first and last generations available in the Pokémon database.
+const MIN_GENERATION: u8 = 1;
+const MAX_GENERATION: u8 = 9;
+/// The generation of the Pokémon the database gives none for.
+const UNKNOWN_GENERATION: u8 = 0;
 
-fn display_shiny_log(log_path: &str) -> Result<(), Error> {
+/// Prints the shiny log, then a summary of the encounters it took when recorded.
+///
+/// # Parameters
+/// - `log_path`: The path of the shiny log file.
+/// - `since`: An optional date; only the entries logged on or after it are shown.
+///
+/// # Returns
+/// - `Result<(), Error>`: Returns `Ok(())` on success, or an `Error` if the log cannot be read.
+fn display_shiny_log(log_path: &str, since: Option<NaiveDate>) -> Result<(), Error> {
     let log_entries = shiny_hunting::load_shiny_log(log_path)?;
+    let mut warned = false;
+    let mut hunts = Vec::new();
 
     for entry in log_entries {
+        if let Some(since) = since {
+            match shiny_hunting::parse_entry_date(&entry.date) {
+                Some(date) if date >= since => {}
+                Some(_) => continue,
+                None => {
+                    if !warned {
+                        eprintln!("Skipping shiny log entries with unparseable dates");
+                        warned = true;
+                    }
+                    continue;
+                }
+            }
+        }
+
+        match entry.encounters {
+            Some(encounters) => println!(
+                "{}: {} {} - {} ({} encounters)",
+                entry.date, entry.pokemon_name, entry.form, entry.details, encounters
+            ),
+            None => println!(
+                "{}: {} {} - {}",
+                entry.date, entry.pokemon_name, entry.form, entry.details
+            ),
+        }
+
+        if let Some(encounters) = entry.encounters {
+            hunts.push((entry.pokemon_name, encounters));
+        }
+    }
+
+    // Entries from older logs have no encounter count and are left out of the summary
+    let luckiest = hunts.iter().min_by_key(|(_, encounters)| *encounters);
+    let unluckiest = hunts.iter().max_by_key(|(_, encounters)| *encounters);
+    if let (Some(luckiest), Some(unluckiest)) = (luckiest, unluckiest) {
+        let total: u64 = hunts.iter().map(|(_, encounters)| *encounters as u64).sum();
+        println!();
+        println!(
+            "Average encounters: {:.1} over {} shiny",
+            total as f64 / hunts.len() as f64,
+            hunts.len()
+        );
+        println!("Luckiest: {} ({} encounters)", luckiest.0, luckiest.1);
+        println!("Unluckiest: {} ({} encounters)", unluckiest.0, unluckiest.1);
+    }
+
+    Ok(())
+}
+
+/// Writes the art of a Pokémon to standard output exactly as stored, for other programs to embed.
+///
+/// Nothing is added or transformed, not even for `--no-color`. For animated art,
+/// the first frame is written.
+///
+/// # Parameters
+/// - `sprite`: A reference to the `cli::Sprite` struct selecting the Pokémon, form and shininess.
+/// - `config`: A reference to the `Config` struct, for the assets directory.
+///
+/// # Returns
+/// - `Result<(), Error>`: Returns `Ok(())` on success, or an `Error` if the art does not exist
+///   or cannot be written.
+fn print_sprite(sprite: &cli::Sprite, config: &Config) -> Result<(), Error> {
+    let slug = match forms::canonical_form(&sprite.form.to_lowercase()) {
+        "regular" => sprite.name.to_lowercase(),
+        form => format!("{}-{}", sprite.name.to_lowercase(), form),
+    };
+    let frames = Assets::from_config(config).get_frames(&art_path(&slug, sprite.shiny));
+    let art = frames.first().ok_or(Error::InvalidPokemon(slug))?;
+
+    let mut out = io::stdout().lock();
+    out.write_all(art)?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Shows two Pokémon side by side, the right one mirrored to face the left one.
+///
+/// # Parameters
+/// - `battle`: A reference to the `cli::Battle` struct selecting the Pokémon and layout.
+/// - `pokemon_db`: The entire Pokémon database, indexed by slug.
+/// - `config`: A reference to the `Config` struct, for the language and assets directory.
+///
+/// # Returns
+/// - `Result<(), Error>`: Returns `Ok(())` on success, or an `Error` if either Pokémon
+///   is unknown or has no art.
+fn show_battle(battle: &cli::Battle, pokemon_db: &PokemonDb, config: &Config) -> Result<(), Error> {
+    let assets = Assets::from_config(config);
+    let load = |name: &str| -> Result<(&Pokemon, String), Error> {
+        let slug = name.to_lowercase();
+        let pokemon = pokemon_db
+            .get(&slug)
+            .ok_or_else(|| Error::InvalidPokemon(slug.clone()))?;
+        let frames = assets.get_frames(&art_path(&slug, battle.shiny));
+        let frame = frames.first().ok_or(Error::InvalidPokemon(slug))?;
+        let art = str::from_utf8(frame).expect("Invalid UTF-8 in pokemon art");
+        Ok((pokemon, art.to_string()))
+    };
+    let (left, left_art) = load(&battle.left)?;
+    let (right, right_art) = load(&battle.right)?;
+    let right_art = ascii::mirror_art(&right_art);
+
+    let mut out = io::stdout().lock();
+    if !battle.no_title {
+        // Each name above its own art
+        let left_width = left_art
+            .lines()
+            .map(ascii::visible_width)
+            .max()
+            .unwrap_or(0);
+        let left_name = left.localized_name(&config.languages())?;
+        let padding = (left_width + battle.gap).saturating_sub(ascii::visible_width(left_name));
+        writeln!(
+            out,
+            "{left_name}{}{}",
+            " ".repeat(padding.max(1)),
+            right.localized_name(&config.languages())?
+        )?;
+    }
+    write!(
+        out,
+        "{}",
+        ascii::side_by_side(&left_art, &right_art, battle.gap)
+    )?;
+    Ok(())
+}
+
+/// Prints the Pokédex entry of a Pokémon, without its art.
+///
+/// # Parameters
+/// - `describe`: A reference to the `cli::Describe` struct selecting the Pokémon and entry.
+/// - `pokemon_db`: The entire Pokémon database, indexed by slug.
+/// - `config`: A reference to the `Config` struct containing the language.
+/// - `rng`: The random number generator picking the entry when none is chosen.
+///
+/// # Returns
+/// - `Result<(), Error>`: Returns `Ok(())` on success, or an `Error` if the Pokémon is unknown.
+fn describe_pokemon(
+    describe: &cli::Describe,
+    pokemon_db: &PokemonDb,
+    config: &Config,
+    rng: &mut impl Rng,
+) -> Result<(), Error> {
+    let pokemon = lookup_pokemon(&describe.name, pokemon_db)?;
+
+    let desc_lines = description::select_description(
+        pokemon,
+        config,
+        &describe.game_info,
+        describe.desc_index,
+        rng,
+    )?
+    .map(|(_, lines)| lines)
+    .unwrap_or_default();
+    if desc_lines.is_empty() {
+        println!("{}", description::missing_notice(pokemon, config));
+    }
+    for line in desc_lines {
+        println!("{}", line.trim_end());
+    }
+    Ok(())
+}
+
+/// Records a shiny capture in the shiny log, dated now.
+///
+/// # Parameters
+/// - `catch`: A reference to the `cli::Catch` struct describing the capture.
+/// - `pokemon_db`: The entire Pokémon database, used to validate the name.
+/// - `log_path`: The path of the shiny log file.
+///
+/// # Returns
+/// - `Result<(), Error>`: Returns `Ok(())` on success, or an `Error` if the Pokémon is
+///   unknown or the log cannot be written.
+fn log_shiny_catch(
+    catch: &cli::Catch,
+    pokemon_db: &PokemonDb,
+    log_path: &str,
+) -> Result<(), Error> {
+    let pokemon = lookup_pokemon(&catch.name, pokemon_db)?;
+    // A form suffix on the name (e.g. `vulpix-alola`) stands for the form
+    let name = catch.name.to_lowercase();
+    let form = match name.strip_prefix(&format!("{}-", pokemon.slug)) {
+        Some(suffix) if catch.form == "regular" => suffix.to_string(),
+        _ => catch.form.to_lowercase(),
+    };
+
+    let entry = shiny_hunting::ShinyLogEntry {
+        pokemon_name: pokemon.slug.clone(),
+        form: forms::canonical_form(&form).to_string(),
+        date: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        details: catch.details.clone(),
+        encounters: catch.encounters,
+    };
+    shiny_hunting::log_shiny_capture(log_path, &entry)?;
+
+    println!("Logged a shiny {} in {}", entry.pokemon_name, log_path);
+    Ok(())
+}
+
+/// Counts an encounter in the shiny hunt for a Pokémon, or ends the hunt with `--reset`.
+///
+/// # Parameters
+/// - `hunt`: A reference to the `cli::Hunt` struct naming the Pokémon hunted.
+/// - `pokemon_db`: The entire Pokémon database, used to validate the name.
+/// - `counters_path`: The path of the hunt counters file.
+///
+/// # Returns
+/// - `Result<(), Error>`: Returns `Ok(())` on success, or an `Error` if the Pokémon is
+///   unknown or the counters cannot be read or written.
+fn count_hunt_encounter(
+    hunt: &cli::Hunt,
+    pokemon_db: &PokemonDb,
+    counters_path: &Path,
+) -> Result<(), Error> {
+    let slug = &lookup_pokemon(&hunt.name, pokemon_db)?.slug;
+
+    let mut counters = shiny_hunting::load_hunt_counters(counters_path)?;
+    if hunt.reset {
+        if let Some(encounters) = counters.remove(slug) {
+            println!("Ended the hunt for {slug} after {encounters} encounters");
+        }
+    } else {
+        let encounters = counters.entry(slug.clone()).or_insert(0);
+        *encounters += 1;
+        println!("{slug}: {encounters} encounters");
+    }
+    shiny_hunting::save_hunt_counters(counters_path, &counters)?;
+    Ok(())
+}
+
+/// Prints the chance of having found at least one shiny after some encounters, and the
+/// encounters needed to reach 50%, 90% and 99%.
+///
+/// # Parameters
+/// - `odds`: A reference to the `cli::Odds` struct with the method and encounters.
+/// - `config`: A reference to the `Config` struct, whose shiny rate is used without a method.
+fn show_shiny_odds(odds: &cli::Odds, config: &Config) {
+    let (rate, source) = match odds.method {
+        Some(method) => (
+            method.rate(),
+            method
+                .to_possible_value()
+                .map(|value| value.get_name().to_string())
+                .unwrap_or_default(),
+        ),
+        None => (config.shiny_rate, "configured shiny rate".to_string()),
+    };
+    if rate > 0.0 {
+        println!("Rate: 1 in {:.0} ({source})", 1.0 / rate);
+    } else {
+        println!("Rate: 0 ({source})");
+    }
+    println!(
+        "Chance of a shiny after {} encounters: {:.2}%",
+        odds.encounters,
+        shiny_hunting::shiny_probability(rate, odds.encounters) * 100.0
+    );
+    for probability in [0.5, 0.9, 0.99] {
+        match shiny_hunting::encounters_for(rate, probability) {
+            Some(encounters) => {
+                println!("Encounters for {:.0}%: {encounters}", probability * 100.0)
+            }
+            None => println!("Encounters for {:.0}%: never", probability * 100.0),
+        }
+    }
+}
+
+/// Prints a summary of today's shiny hunting: the active hunts with their counters,
+/// the shiny caught today and the total number of shiny in the log.
+///
+/// # Parameters
+/// - `log_path`: The path of the shiny log file, which may not exist yet.
+/// - `counters_path`: The path of the hunt counters file, which may not exist yet.
+///
+/// # Returns
+/// - `Result<(), Error>`: Returns `Ok(())` on success, or an `Error` if a file cannot be read.
+fn display_today(log_path: &str, counters_path: &Path) -> Result<(), Error> {
+    let today = Local::now().date_naive();
+    let log_entries = match shiny_hunting::load_shiny_log(log_path) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let counters = shiny_hunting::load_hunt_counters(counters_path)?;
+
+    println!("Shiny hunting on {}", today.format("%Y-%m-%d"));
+
+    if counters.is_empty() {
+        println!("No active hunt");
+    } else {
+        println!("Active hunts:");
+        for (slug, encounters) in &counters {
+            println!("  {:<20} {} encounters", slug, encounters);
+        }
+    }
+
+    let caught_today: Vec<&str> = log_entries
+        .iter()
+        .filter(|entry| shiny_hunting::parse_entry_date(&entry.date) == Some(today))
+        .map(|entry| entry.pokemon_name.as_str())
+        .collect();
+    if caught_today.is_empty() {
+        println!("Caught today: 0");
+    } else {
         println!(
-            "{}: {} {} - {}",
-            entry.date, entry.pokemon_name, entry.form, entry.details
+            "Caught today: {} ({})",
+            caught_today.len(),
+            caught_today.join(", ")
         );
     }
+    println!("Total shiny: {}", log_entries.len());
 
     Ok(())
 }
@@ -84,7 +414,20 @@ fn track_encounter(tracker_path: &str, pokemon_name: &str, unique: bool) -> Resu
     Ok(())
 }
 
-fn show_completion_status(tracker_path: &str, total_pokemon: usize) -> Result<(), Error> {
+/// Represents the Pokédex completion, as printed by `show-completion --json`.
+#[derive(Serialize, Debug)]
+struct CompletionStatus {
+    unique: usize,
+    total: usize,
+    percentage: f64,
+    missing: Vec<String>,
+}
+
+fn show_completion_status(
+    tracker_path: &str,
+    pokemon_db: &[Pokemon],
+    json: bool,
+) -> Result<(), Error> {
     // Load existing encounters
     let tracker = if let Ok(file_content) = std::fs::read_to_string(tracker_path) {
         serde_json::from_str::<EncounteredPokemonTracker>(&file_content)
@@ -94,6 +437,7 @@ fn show_completion_status(tracker_path: &str, total_pokemon: usize) -> Result<()
     };
 
     let unique_count = tracker.encounters.len();
+    let total_pokemon = pokemon_db.len();
 
     // Calculate the percentage of the Pokédex completion
     let completion_percentage = if total_pokemon > 0 {
@@ -102,6 +446,23 @@ fn show_completion_status(tracker_path: &str, total_pokemon: usize) -> Result<()
         0.0
     };
 
+    if json {
+        // Species of the db that are not in the tracker
+        let missing = pokemon_db
+            .iter()
+            .filter(|p| !tracker.encounters.iter().any(|e| e.name == p.slug))
+            .map(|p| p.slug.clone())
+            .collect();
+        let status = CompletionStatus {
+            unique: unique_count,
+            total: total_pokemon,
+            percentage: completion_percentage,
+            missing,
+        };
+        println!("{}", serde_json::to_string_pretty(&status)?);
+        return Ok(());
+    }
+
     println!("You have encountered {} unique Pokémon.", unique_count);
     println!(
         "Pokedex completion: {:.2}% ({} out of {})",
@@ -110,6 +471,179 @@ fn show_completion_status(tracker_path: &str, total_pokemon: usize) -> Result<()
 
     Ok(())
 }
+
+/// Parses the generations argument of the random command.
+///
+/// The argument is either a range (`1-3`) or a comma-separated list of
+/// generations (`1,3,6`). Every number must be a valid generation and ranges
+/// must not be reversed.
+///
+/// # Parameters
+/// - `generations`: The generations argument as typed by the user.
+///
+/// # Returns
+/// - `Result<Vec<RangeInclusive<u8>>, Error>`: A single range for the range syntax, or one
+///   single-generation range per list entry, or an `Error::InvalidGeneration` explaining
+///   what is wrong with the argument.
+fn parse_generations(generations: &str) -> Result<Vec<RangeInclusive<u8>>, Error> {
+    let invalid = |reason: &str| Error::InvalidGeneration(generations.to_string(), reason.into());
+    let parse = |gen: &str| match gen.trim().parse::<u8>() {
+        Ok(val) if (MIN_GENERATION..=MAX_GENERATION).contains(&val) => Ok(val),
+        _ => Err(invalid("should be integers between 1 and 9")),
+    };
+
+    match generations.split_once('-') {
+        Some((start, end)) => {
+            let (start, end) = (parse(start)?, parse(end)?);
+            if start > end {
+                return Err(invalid(
+                    "the range is reversed, the first generation should not be greater than the last",
+                ));
+            }
+            Ok(vec![start..=end])
+        }
+        None => generations
+            .split(',')
+            .map(|gen| parse(gen).map(|val| val..=val))
+            .collect(),
+    }
+}
+
+/// Parses a National Dex range, e.g. `1-151`, or a single number.
+///
+/// Numbers start at 1 and ranges must not be reversed, like for the generations.
+///
+/// # Parameters
+/// - `dex`: The range as typed by the user.
+///
+/// # Returns
+/// - `Result<RangeInclusive<u16>, Error>`: The range, or an `Error::InvalidDexRange`
+///   explaining what is wrong with the argument.
+fn parse_dex_range(dex: &str) -> Result<RangeInclusive<u16>, Error> {
+    let invalid = |reason: &str| Error::InvalidDexRange(dex.to_string(), reason.into());
+    let parse = |number: &str| match number.trim().parse::<u16>() {
+        Ok(val) if val >= 1 => Ok(val),
+        _ => Err(invalid("should be positive integers")),
+    };
+
+    match dex.split_once('-') {
+        Some((start, end)) => {
+            let (start, end) = (parse(start)?, parse(end)?);
+            if start > end {
+                return Err(invalid(
+                    "the range is reversed, the first number should not be greater than the last",
+                ));
+            }
+            Ok(start..=end)
+        }
+        None => parse(dex).map(|val| val..=val),
+    }
+}
+
+/// Keeps the Pokémon whose National Dex number is in the given range, if any.
+///
+/// The end of the range is clamped to the last Pokémon of the database.
+///
+/// # Parameters
+/// - `pokemon_db`: The Pokémon database to filter.
+/// - `dex`: The optional range as typed by the user.
+///
+/// # Returns
+/// - `Result<Vec<Pokemon>, Error>`: The Pokémon in the range, or an `Error::InvalidDexRange`
+///   if the range is invalid or starts after the last Pokémon.
+fn filter_dex_range(pokemon_db: PokemonDb, dex: Option<&str>) -> Result<Vec<Pokemon>, Error> {
+    let Some(dex) = dex else {
+        return Ok(pokemon_db.into_vec());
+    };
+
+    let range = parse_dex_range(dex)?;
+    let last = pokemon_db.iter().map(|p| p.idx).max().unwrap_or(0);
+    if *range.start() > last {
+        return Err(Error::InvalidDexRange(
+            dex.to_string(),
+            format!("the database ends at #{last}"),
+        ));
+    }
+
+    let range = *range.start()..=(*range.end()).min(last);
+    Ok(pokemon_db
+        .into_vec()
+        .into_iter()
+        .filter(|p| range.contains(&p.idx))
+        .collect())
+}
+
+/// Collapses the entries that are a form of another entry with `--species-only`, reporting
+/// how many were collapsed on standard error.
+///
+/// # Parameters
+/// - `pokemon`: The Pokémon listed or exported.
+/// - `species_only`: Whether to collapse the forms, the Pokémon being returned as is otherwise.
+///
+/// # Returns
+/// - `Vec<Pokemon>`: The Pokémon left.
+fn collapse_forms(pokemon: Vec<Pokemon>, species_only: bool) -> Vec<Pokemon> {
+    if !species_only {
+        return pokemon;
+    }
+    let (species, collapsed) = pokemon::species_only(pokemon);
+    eprintln!("Collapsed {collapsed} form row(s) into their species");
+    species
+}
+
+/// Shows the Pokémon of the period, the day by default.
+///
+/// The random selection is seeded with the current local date, so every
+/// invocation in the same period picks the same Pokémon (and shiny roll) given the
+/// same filters, regardless of the machine.
+///
+/// # Parameters
+/// - `daily`: A reference to the `cli::Daily` struct containing the selection filters and the period.
+/// - `pokemon_db`: The entire Pokémon database, indexed by slug.
+/// - `config`: A reference to the `Config` struct containing configuration settings such as shiny rate.
+///
+/// # Returns
+/// - `Result<(), Error>`: Returns an `Ok(())` if successful, or an `Error` if any issues occur
+///   during the filtering or selection process.
+fn show_daily_pokemon(
+    daily: &cli::Daily,
+    pokemon_db: &PokemonDb,
+    config: &Config,
+) -> Result<(), Error> {
+    let seed = period_seed(daily.period, Local::now().naive_local());
+
+    show_random_pokemon(
+        &daily.random,
+        pokemon_db,
+        config,
+        &mut StdRng::seed_from_u64(seed),
+    )
+}
+
+/// Returns the seed shared by every moment of a period, the same on every machine.
+///
+/// The seeds are YYYYMMDDHH for hours, YYYYMMDD for days and YYYYWW (ISO week) for weeks,
+/// so different periods never share a seed.
+///
+/// # Parameters
+/// - `period`: The period the moment is truncated to.
+/// - `now`: The moment, in local time.
+///
+/// # Returns
+/// - `u64`: The seed of the period containing `now`.
+fn period_seed(period: cli::Period, now: NaiveDateTime) -> u64 {
+    let date = now.date();
+    let day = (date.year() as u64) * 10_000 + (date.month() as u64) * 100 + date.day() as u64;
+    match period {
+        cli::Period::Hourly => day * 100 + now.hour() as u64,
+        cli::Period::Daily => day,
+        cli::Period::Weekly => {
+            let week = date.iso_week();
+            (week.year() as u64) * 100 + week.week() as u64
+        }
+    }
+}
+
 /// Shows a random Pokémon based on user-defined criteria such as generation range, forms, and shiny status.
 ///
 /// This function filters the Pokémon database according to the specified generation range
@@ -117,57 +651,288 @@ fn show_completion_status(tracker_path: &str, total_pokemon: usize) -> Result<()
 /// these criteria and prepares its representation, including potential shiny variants and
 /// form variations. Finally, it calls another function to display the chosen Pokémon's information.
 ///
+/// With `--count`, this is repeated for every Pokémon. With `--progress`, each completed pick is
+/// reported on standard error once its art has been flushed to standard output.
+///
 /// # Parameters
 /// - `random`: A reference to the `cli::Random` struct containing user preferences for random Pokémon selection.
-/// - `pokemon_db`: A vector of `Pokemon` objects representing the entire Pokémon database.
+/// - `pokemon_db`: The entire Pokémon database, indexed by slug.
 /// - `config`: A reference to the `Config` struct containing configuration settings such as shiny rate.
+/// - `rng`: The random number generator driving the selection and the shiny roll.
 ///
 /// # Returns
 /// - `Result<(), Error>`: Returns an `Ok(())` if successful, or an `Error` if any issues occur
 ///   during the filtering or selection process.
-
 fn show_random_pokemon(
     random: &cli::Random,
-    pokemon_db: Vec<Pokemon>,
+    pokemon_db: &PokemonDb,
     config: &Config,
+    rng: &mut impl Rng,
 ) -> Result<(), Error> {
-    const MAX_RETRIES: usize = 10; // Avoid infinite loops
-
-    for _ in 0..MAX_RETRIES {
-        // Determine generation range
-        let (start_gen, end_gen) = match random.generations.split_once('-') {
-            Some((start, end)) => (start, end),
-            None => {
-                let gen_list = random.generations.split(',').collect::<Vec<_>>();
-                let gen = gen_list.choose(&mut rand::rng()).unwrap_or(&"1");
-                (*gen, *gen)
+    // A per-invocation shiny chance takes precedence over the configured rate
+    let shiny_rate = random.shiny_chance.unwrap_or(config.shiny_rate);
+    if !(0.0..=1.0).contains(&shiny_rate) {
+        return Err(Error::InvalidShinyChance(shiny_rate));
+    }
+
+    // Older datasets have no categories, which would leave nothing to pick
+    if !random_categories(random).is_empty() && pokemon_db.iter().all(|p| p.category.is_empty()) {
+        return Err(Error::MissingData("categories".into()));
+    }
+
+    let mut generations = parse_generations(&random.generations())?;
+    // Pokémon without a generation join the picks only on request
+    if random.include_unknown_gen {
+        generations.push(UNKNOWN_GENERATION..=UNKNOWN_GENERATION);
+    }
+
+    for slug in &random.exclude {
+        if pokemon_db.get(slug).is_none() {
+            eprintln!("Ignoring excluded `{slug}`, it is not a known Pokémon");
+        }
+    }
+
+    // With --from-file only the listed Pokémon can be picked, as if every other was excluded
+    let mut exclude = random.exclude.clone();
+    if let Some(path) = &random.from_file {
+        let pool = read_pool(path, pokemon_db)?;
+        exclude.extend(
+            pokemon_db
+                .iter()
+                .filter(|p| !pool.contains(&p.slug))
+                .map(|p| p.slug.clone()),
+        );
+    }
+    let excluded = exclude.len();
+
+    // With --unique-run the Pokémon already shown are excluded too
+    for done in 1..=random.count {
+        // Start over once a generation range has nothing new left to show
+        if random.unique_run && exclude.len() > excluded {
+            let categories = random_categories(random);
+            let exhausted = picked_ranges(&generations).0.iter().any(|range| {
+                !pokemon_db.iter().any(|p| {
+                    in_generation(p, range, random.include_unknown_gen)
+                        && !exclude.contains(&p.slug)
+                        && in_categories(p, &categories)
+                })
+            });
+            if exhausted {
+                eprintln!("Every Pokémon has been shown, allowing repeats from now on");
+                exclude.truncate(excluded);
             }
-        };
+        }
 
-        // Parse start and end generations
-        let start_gen = match start_gen.parse::<u8>() {
-            Ok(val) => val,
-            Err(_) => return Err(Error::InvalidGeneration(random.generations.clone())),
-        };
-        let end_gen = match end_gen.parse::<u8>() {
-            Ok(val) => val,
-            Err(_) => return Err(Error::InvalidGeneration(random.generations.clone())),
+        let slug = show_one_random_pokemon(
+            random,
+            &generations,
+            &exclude,
+            pokemon_db,
+            config,
+            shiny_rate,
+            rng,
+        )?;
+        if random.unique_run {
+            exclude.push(slug.clone());
+        }
+
+        if random.progress {
+            // Flush the art first so the progress line never lands in the middle of it
+            io::stdout().flush()?;
+            eprintln!("[{done}/{}] {slug}", random.count);
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads the pool of Pokémon given with `--from-file`, warning about the slugs that are not
+/// in the database.
+///
+/// Blank lines and lines starting with `#` are skipped, and slugs are matched ignoring case.
+///
+/// # Parameters
+/// - `path`: The file listing one slug per line.
+/// - `pokemon_db`: The entire Pokémon database, indexed by slug.
+///
+/// # Returns
+/// - `Result<HashSet<String>, Error>`: The known slugs of the file, or an `Error` if it cannot be read.
+fn read_pool(path: &Path, pokemon_db: &PokemonDb) -> Result<HashSet<String>, Error> {
+    let content =
+        fs::read_to_string(path).map_err(|e| Error::Io(format!("{}: {e}", path.display())))?;
+
+    let mut pool = HashSet::new();
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let slug = line.to_lowercase();
+        if pokemon_db.get(&slug).is_some() {
+            pool.insert(slug);
+        } else {
+            eprintln!(
+                "Ignoring `{line}` in {}, it is not a known Pokémon",
+                path.display()
+            );
+        }
+    }
+    Ok(pool)
+}
+
+/// Returns the categories the random Pokémon are restricted to by `--legendary` and `--mythical`.
+fn random_categories(random: &cli::Random) -> Vec<&'static str> {
+    let mut categories = Vec::new();
+    if random.legendary {
+        categories.push("legendary");
+    }
+    if random.mythical {
+        categories.push("mythical");
+    }
+    categories
+}
+
+/// Returns whether a Pokémon belongs to one of the categories, any Pokémon matching no category.
+fn in_categories(pokemon: &Pokemon, categories: &[&str]) -> bool {
+    categories.is_empty() || categories.contains(&pokemon.category.as_str())
+}
+
+/// Splits generation ranges into the ranges picked from and whether the unknown generation
+/// is among them, as it joins whichever range is picked instead of being picked on its own.
+fn picked_ranges(generations: &[RangeInclusive<u8>]) -> (Vec<&RangeInclusive<u8>>, bool) {
+    let unknown = UNKNOWN_GENERATION..=UNKNOWN_GENERATION;
+    let ranges = generations.iter().filter(|r| **r != unknown).collect();
+    (ranges, generations.contains(&unknown))
+}
+
+/// Returns whether a Pokémon belongs to a generation range, or to the unknown generation
+/// when `include_unknown` is set.
+fn in_generation(pokemon: &Pokemon, range: &RangeInclusive<u8>, include_unknown: bool) -> bool {
+    range.contains(&pokemon.gen) || include_unknown && pokemon.gen == UNKNOWN_GENERATION
+}
+
+/// Picks a random Pokémon from the given generations, biased by the weights from the config.
+///
+/// # Parameters
+/// - `generations_arg`: The generations as given on the command line, for error messages.
+/// - `generations`: The generation ranges to pick from. One range is chosen first, then a Pokémon in it.
+///   The Pokémon of the unknown generation join the chosen range when `0..=0` is among them.
+/// - `exclude`: The slugs of the Pokémon that must not be picked.
+/// - `categories`: The categories the Pokémon must belong to (e.g. `legendary`), any if empty.
+/// - `pokemon_db`: The entire Pokémon database.
+/// - `config`: A reference to the `Config` struct containing the weights.
+/// - `rng`: The random number generator driving the selection.
+///
+/// # Returns
+/// - `Result<&Pokemon, Error>`: The picked Pokémon, or an `Error` if the chosen range has no
+///   Pokémon left or the weights are invalid.
+fn pick_random_pokemon<'a>(
+    generations_arg: &str,
+    generations: &[RangeInclusive<u8>],
+    exclude: &[String],
+    categories: &[&str],
+    pokemon_db: &'a [Pokemon],
+    config: &Config,
+    rng: &mut impl Rng,
+) -> Result<&'a Pokemon, Error> {
+    // Determine generation range
+    let (ranges, include_unknown) = picked_ranges(generations);
+    let range = ranges.choose(rng).copied().unwrap_or(&(1..=1));
+
+    // Filter Pokémon by generation and category, leaving out the excluded ones
+    let pokemon: Vec<&Pokemon> = pokemon_db
+        .iter()
+        .filter(|p| in_generation(p, range, include_unknown) && !exclude.contains(&p.slug))
+        .filter(|p| in_categories(p, categories))
+        .collect();
+
+    if pokemon.is_empty() {
+        let kind = match categories {
+            [] => String::new(),
+            categories => format!("{} ", categories.join(" or ")),
         };
+        return Err(Error::InvalidGeneration(
+            generations_arg.to_string(),
+            format!("no {kind}Pokémon found in these generations"),
+        ));
+    }
 
-        // Filter Pokémon by generation
-        let pokemon: Vec<&Pokemon> = pokemon_db
-            .iter()
-            .filter(|p| start_gen <= p.gen && end_gen >= p.gen)
-            .collect();
+    // Pick a Pokémon, biased by the weights from the config
+    match pokemon.choose_weighted(rng, |p| config.weight(p)) {
+        Ok(&p) => Ok(p),
+        Err(e) => Err(Error::Configuration(format!("Invalid weights: {e}"))),
+    }
+}
+
+/// Picks and shows a single random Pokémon, retrying when its art cannot be shown.
+///
+/// The form is picked among the forms with art, weighted by `Config::form_weight`, after
+/// leaving out the forms excluded by `--no-mega`, `--no-gmax` and `--no-regional`.
+///
+/// # Parameters
+/// - `random`: A reference to the `cli::Random` struct containing user preferences for random Pokémon selection.
+/// - `generations`: The generation ranges to pick from.
+/// - `exclude`: The slugs of the Pokémon that must not be picked.
+/// - `pokemon_db`: The entire Pokémon database.
+/// - `config`: A reference to the `Config` struct containing configuration settings such as weights.
+/// - `shiny_rate`: The probability of showing the shiny version.
+/// - `rng`: The random number generator driving the selection and the shiny roll.
+///
+/// # Returns
+/// - `Result<String, Error>`: The slug of the Pokémon shown, or an `Error` if none could be shown.
+fn show_one_random_pokemon(
+    random: &cli::Random,
+    generations: &[RangeInclusive<u8>],
+    exclude: &[String],
+    pokemon_db: &PokemonDb,
+    config: &Config,
+    shiny_rate: f64,
+    rng: &mut impl Rng,
+) -> Result<String, Error> {
+    // Bounded to avoid infinite loops when the filters leave no art to show
+    let max_retries = random
+        .max_retries
+        .map_or(config.max_retries, |retries| retries as usize);
+
+    let assets = Assets::from_config(config);
+
+    for _ in 0..max_retries {
+        let selected_pokemon = pick_random_pokemon(
+            &random.generations(),
+            generations,
+            exclude,
+            &random_categories(random),
+            pokemon_db,
+            config,
+            rng,
+        )?;
 
-        let selected_pokemon = match pokemon.choose(&mut rand::rng()) {
-            Some(&p) => p,
-            None => return Err(Error::InvalidGeneration(random.generations.clone())),
+        let shiny = random.shiny || rng.random_bool(shiny_rate);
+
+        // Pick a form with art, alternate forms being rarer than the regular one
+        let forms: Vec<String> = available_forms(
+            selected_pokemon,
+            &selected_pokemon.slug,
+            pokemon_db,
+            &assets,
+            shiny && !random.shiny_fallback,
+        )
+        .into_iter()
+        .filter(|form| {
+            !(random.no_mega && forms::is_mega(form)
+                || random.no_gmax && forms::is_gmax(form)
+                || random.no_regional && forms::is_regional(form))
+                && config.form_weight(form) != 0.0
+        })
+        .collect();
+        if forms.is_empty() {
+            continue; // no art left to show, pick another Pokémon
+        }
+        let form = match forms.choose_weighted(rng, |form| config.form_weight(form)) {
+            Ok(form) => form.clone(),
+            Err(e) => return Err(Error::Configuration(format!("Invalid form weights: {e}"))),
         };
 
         // Try showing the Pokémon
-        let form = "regular".to_string(); // Keep your form logic here
-        let shiny = rand::rng().random_bool(config.shiny_rate) || random.shiny;
 
         let game_name = if random.game_info.is_empty() {
             String::new()
@@ -178,157 +943,1051 @@ fn show_random_pokemon(
         let result = show_pokemon_by_name(
             &cli::Name {
                 name: selected_pokemon.slug.clone(),
-                form: form.clone(),
+                form,
                 shiny,
+                no_shiny: random.no_shiny,
+                shiny_fallback: random.shiny_fallback,
                 info: random.info,
+                no_info: random.no_info,
                 game_info: game_name,
                 under: random.under,
+                no_under: random.no_under,
                 no_title: random.no_title,
                 padding_left: random.padding_left,
+                padding_top: random.padding_top,
                 stats: random.stats,
+                radar: random.radar,
+                show_types: random.show_types,
+                show_dex: random.show_dex,
+                list_forms: false,
+                track: false,
+                desc_index: random.desc_index,
+                desc_max_lines: random.desc_max_lines,
+                frame: random.frame,
+                mirror: random.mirror,
+                color256: random.color256,
+                palette: random.palette,
+                ascii: random.ascii,
+                delay: random.delay,
+                // Animating runs until interrupted, so only a single Pokémon can be animated
+                animate: random.animate.filter(|_| random.count == 1),
+                output_file: random.output_file.clone(),
                 unique: random.unique,
+                no_desc_color: random.no_desc_color,
+                no_desc_source: random.no_desc_source,
+                quiet: random.quiet,
+                bg: random.bg.clone(),
             },
-            pokemon_db.clone(),
+            pokemon_db,
             config,
+            rng,
         );
 
         if result.is_ok() {
-            return Ok(()); // success
+            return Ok(selected_pokemon.slug.clone()); // success
         }
         // else, loop and try again
     }
 
-    Err(Error::InvalidPokemon(
-        "Too many failed attempts".to_string(),
-    ))
+    Err(Error::TooManyAttempts(max_retries, active_filters(random)))
 }
 
-/// Displays information about a Pokémon based on its name and specified form.
+/// Describes the options of the random command narrowing down what can be shown,
+/// to tell why no pick could be shown.
 ///
-/// This function searches for a Pokémon in the database using its slug (name).
-/// If found, it retrieves and displays the Pokémon's ASCII art, potential
-/// descriptions, and stats if requested. The function handles shiny variants
-/// and different forms of the Pokémon, as well as optional titles and game
-/// information based on user input.
+/// # Parameters
+/// - `random`: A reference to the `cli::Random` struct holding the options.
+///
+/// # Returns
+/// - `String`: The active filters separated by commas, starting with the generations.
+fn active_filters(random: &cli::Random) -> String {
+    let mut filters = vec![format!("generations {}", random.generations())];
+    if random.include_unknown_gen {
+        filters.push("include-unknown-gen".to_string());
+    }
+    if random.shiny && !random.shiny_fallback {
+        filters.push("shiny".to_string());
+    }
+    for (set, flag) in [
+        (random.no_mega, "no-mega"),
+        (random.no_gmax, "no-gmax"),
+        (random.no_regional, "no-regional"),
+        (random.legendary, "legendary"),
+        (random.mythical, "mythical"),
+    ] {
+        if set {
+            filters.push(flag.to_string());
+        }
+    }
+    if !random.exclude.is_empty() {
+        filters.push(format!("exclude {}", random.exclude.join(",")));
+    }
+    if let Some(path) = &random.from_file {
+        filters.push(format!("from-file {}", path.display()));
+    }
+    filters.join(", ")
+}
+
+/// Runs the "Who's that Pokémon!?" quiz.
+///
+/// A random Pokémon is picked the same way as with the random command, and its
+/// silhouette is shown. The guess read from standard input is compared, ignoring
+/// case, with the slug and the name in the configured language, then the colored
+/// art is revealed with the correct name.
+///
+/// # Parameters
+/// - `whosthat`: A reference to the `cli::WhosThat` struct containing the generations to pick from.
+/// - `pokemon_db`: The entire Pokémon database, indexed by slug.
+/// - `config`: A reference to the `Config` struct containing configuration settings such as weights.
+/// - `rng`: The random number generator driving the selection.
+///
+/// # Returns
+/// - `Result<(), Error>`: Returns `Ok(())` once the answer is revealed, or an `Error` if no
+///   Pokémon could be picked or standard input could not be read.
+fn play_whos_that_pokemon(
+    whosthat: &cli::WhosThat,
+    pokemon_db: &PokemonDb,
+    config: &Config,
+    rng: &mut impl Rng,
+) -> Result<(), Error> {
+    let generations = parse_generations(&whosthat.generations)?;
+    let assets = Assets::from_config(config);
+
+    // Retry the pick until one has art to draw the silhouette from
+    let mut picked = None;
+    for _ in 0..config.max_retries {
+        let pokemon = pick_random_pokemon(
+            &whosthat.generations,
+            &generations,
+            &[],
+            &[],
+            pokemon_db,
+            config,
+            rng,
+        )?;
+        if let Some(art) = assets
+            .get_frames(&art_path(&pokemon.slug, false))
+            .into_iter()
+            .next()
+        {
+            picked = Some((pokemon, art));
+            break;
+        }
+    }
+    let Some((pokemon, art)) = picked else {
+        return Err(Error::TooManyAttempts(
+            config.max_retries,
+            format!("generations {}", whosthat.generations),
+        ));
+    };
+
+    // Resolved up front so an unsupported language fails before the guess
+    let pokemon_name = pokemon.localized_name(&config.languages())?;
+
+    let art = std::str::from_utf8(&art).expect("Invalid UTF-8 in pokemon art");
+    let mut out = io::stdout().lock();
+    ascii::print_ascii_art(&mut out, &ascii::silhouette_art(art), 0, 0, "")?;
+    write!(out, "Who's that Pokémon!? ")?;
+    out.flush()?;
+    drop(out);
+
+    let mut guess = String::new();
+    io::stdin().read_line(&mut guess)?;
+    let guess = guess.trim().to_lowercase();
+
+    let correct = guess == pokemon.slug || guess == pokemon_name.to_lowercase();
+
+    println!();
+    show_pokemon_by_name(
+        &cli::Name {
+            name: pokemon.slug.clone(),
+            form: "regular".to_string(),
+            shiny: false,
+            no_shiny: false,
+            shiny_fallback: false,
+            info: false,
+            no_info: false,
+            game_info: String::new(),
+            under: false,
+            no_under: false,
+            no_title: true,
+            padding_left: 0,
+            padding_top: 0,
+            stats: false,
+            radar: false,
+            show_types: false,
+            show_dex: false,
+            list_forms: false,
+            track: false,
+            desc_index: None,
+            desc_max_lines: None,
+            frame: false,
+            mirror: false,
+            color256: false,
+            palette: None,
+            ascii: false,
+            delay: None,
+            animate: None,
+            output_file: None,
+            unique: false,
+            no_desc_color: false,
+            no_desc_source: false,
+            quiet: false,
+            bg: None,
+        },
+        pokemon_db,
+        config,
+        rng,
+    )?;
+
+    if correct {
+        println!("That's right, it's {pokemon_name}!");
+    } else {
+        println!("It's {pokemon_name}!");
+    }
+
+    Ok(())
+}
+
+/// Writes output through a pager when asked to and standard output is a terminal.
+///
+/// The pager is taken from `$PAGER`, `less` being used when it is not set. The
+/// output is written directly to standard output when it is not a terminal, so
+/// piping is unaffected, or when the pager cannot be started.
+///
+/// # Parameters
+/// - `page`: Whether paging was requested.
+/// - `write`: The function writing the output.
+///
+/// # Returns
+/// - `Result<(), Error>`: Returns `Ok(())` once the output is written and the pager exited,
+///   or an `Error` if writing fails.
+fn with_pager(
+    page: bool,
+    write: impl FnOnce(&mut dyn Write) -> io::Result<()>,
+) -> Result<(), Error> {
+    let pager = env::var("PAGER")
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| "less".to_string());
+    let mut words = pager.split_whitespace();
+
+    let child = match words.next() {
+        Some(program) if page && io::stdout().is_terminal() => Command::new(program)
+            .args(words)
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(|e| eprintln!("Could not start pager `{pager}`: {e}"))
+            .ok(),
+        _ => None,
+    };
+
+    let result = match child {
+        Some(mut child) => {
+            let result = match child.stdin.take() {
+                Some(mut stdin) => write(&mut stdin),
+                None => Ok(()),
+            };
+            child.wait()?;
+            result
+        }
+        None => write(&mut io::stdout().lock()),
+    };
+
+    // Quitting the pager (or `head`) early closes the pipe, which is not an error
+    match result {
+        Err(e) if e.kind() != io::ErrorKind::BrokenPipe => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Finds the Pokémon a name refers to, the name possibly ending with a form suffix.
+///
+/// The full name is matched first, so hyphenated slugs such as `mr-mime` or `ho-oh`
+/// resolve to themselves. Otherwise trailing `-` segments are dropped one at a time
+/// and treated as a form suffix, e.g. `vulpix-alola` resolves to `vulpix`.
+///
+/// # Parameters
+/// - `name`: The name as typed by the user.
+/// - `pokemon_db`: The entire Pokémon database.
+///
+/// # Returns
+/// - `Option<&Pokemon>`: The Pokémon with the longest slug the name starts with, or `None`.
+fn find_base_pokemon<'a>(name: &str, pokemon_db: &'a PokemonDb) -> Option<&'a Pokemon> {
+    let mut base = name;
+    loop {
+        if let Some(pokemon) = pokemon_db.get(base) {
+            return Some(pokemon);
+        }
+        base = base.rsplit_once('-')?.0;
+    }
+}
+
+/// Looks a Pokémon up by a name typed by the user, ignoring case and a form suffix.
+///
+/// # Parameters
+/// - `name`: The name as typed by the user, e.g. `Pikachu` or `vulpix-alola`.
+/// - `pokemon_db`: The entire Pokémon database.
+///
+/// # Returns
+/// - `Result<&Pokemon, Error>`: The Pokémon, or `Error::InvalidPokemon` if the name matches none.
+fn lookup_pokemon<'a>(name: &str, pokemon_db: &'a PokemonDb) -> Result<&'a Pokemon, Error> {
+    find_base_pokemon(&name.to_lowercase(), pokemon_db)
+        .ok_or_else(|| Error::InvalidPokemon(name.to_string()))
+}
+
+/// Clears the screen and runs `show` every `seconds` seconds, until interrupted with Ctrl-C.
+///
+/// The cursor is hidden while repeating and shown again on Ctrl-C, which exits the process.
+///
+/// # Parameters
+/// - `seconds`: The time between two runs.
+/// - `show`: Shows what is repeated, e.g. a random Pokémon.
+///
+/// # Returns
+/// - `Result<(), Error>`: Only returns on failure, with an `Error` if standard output is not
+///   a terminal, the Ctrl-C handler cannot be set, or `show` fails.
+fn repeat_every(seconds: u64, mut show: impl FnMut() -> Result<(), Error>) -> Result<(), Error> {
+    const HIDE_CURSOR: &str = "\x1b[?25l";
+    const SHOW_CURSOR: &str = "\x1b[?25h";
+
+    // Clearing the screen in a pipe or a file would only pile up output
+    if !io::stdout().is_terminal() {
+        return Err(Error::Io(
+            "--repeat only works with standard output connected to a terminal".to_string(),
+        ));
+    }
+    ctrlc::set_handler(|| {
+        let mut stdout = io::stdout();
+        let _ = write!(stdout, "{SHOW_CURSOR}");
+        let _ = stdout.flush();
+        std::process::exit(130);
+    })
+    .map_err(|e| Error::Io(format!("Failed to handle Ctrl-C: {e}")))?;
+
+    loop {
+        print!("{HIDE_CURSOR}\x1b[2J\x1b[H");
+        let shown = show();
+        if shown.is_err() {
+            print!("{SHOW_CURSOR}");
+        }
+        shown?;
+        io::stdout().flush()?;
+        std::thread::sleep(Duration::from_secs(seconds));
+    }
+}
+
+/// Creates or empties the file given with `--output-file`, which every Pokémon shown is then appended to.
+///
+/// # Parameters
+/// - `path`: The optional path of the output file.
+///
+/// # Returns
+/// - `Result<(), Error>`: Returns `Ok(())` on success, or an `Error` if the file cannot be created.
+fn create_output_file(path: Option<&PathBuf>) -> Result<(), Error> {
+    if let Some(path) = path {
+        fs::File::create(path)?;
+    }
+    Ok(())
+}
+
+/// Returns the path of the embedded art of a Pokémon.
+///
+/// # Parameters
+/// - `slug`: The slug of the Pokémon, including the form suffix if any (e.g. `vulpix-alola`).
+/// - `shiny`: Whether to return the path of the shiny art.
+fn art_path(slug: &str, shiny: bool) -> String {
+    if shiny {
+        format!("colorscripts/shiny/{}", slug)
+    } else {
+        format!("colorscripts/regular/{}", slug)
+    }
+}
+
+/// Lists the forms of a Pokémon that have embedded art.
+///
+/// The forms come from the `forms` field of the Pokémon and from the art files
+/// named after its slug (e.g. `charizard-mega-x`), ignoring files that belong to
+/// another Pokémon whose slug shares the prefix.
+///
+/// # Parameters
+/// - `pokemon`: The Pokémon to list the forms of.
+/// - `slug`: The slug the art files are named after.
+/// - `pokemon_db`: The entire Pokémon database, used to tell forms and other Pokémon apart.
+/// - `assets`: The assets the art is looked up in.
+/// - `shiny`: Whether the forms must have shiny art.
+///
+/// # Returns
+/// - `Vec<String>`: The forms with art, starting with `regular` if the base art exists
+///   and sorted alphabetically after it.
+fn available_forms(
+    pokemon: &Pokemon,
+    slug: &str,
+    pokemon_db: &PokemonDb,
+    assets: &Assets,
+    shiny: bool,
+) -> Vec<String> {
+    let dir = art_path("", shiny);
+    let prefix = format!("{slug}-");
+
+    let from_art = assets.list(&dir).into_iter().filter_map(|file| {
+        // The frames of animated art are named after the directory holding them
+        let name = file.strip_prefix(&dir)?.split('/').next()?;
+        let form = name.strip_prefix(&prefix)?;
+        if pokemon_db.get(name).is_some() {
+            return None;
+        }
+        Some(form.to_string())
+    });
+    let from_data = pokemon
+        .forms
+        .iter()
+        .filter(|form| {
+            !assets
+                .get_frames(&art_path(&format!("{prefix}{form}"), shiny))
+                .is_empty()
+        })
+        .cloned();
+
+    let mut forms: Vec<String> = from_art.chain(from_data).collect();
+    if !assets.get_frames(&art_path(slug, shiny)).is_empty() {
+        forms.push("regular".to_string());
+    }
+    // The regular form first, then the others alphabetically
+    forms.sort_by(|a, b| (a != "regular", a).cmp(&(b != "regular", b)));
+    forms.dedup();
+    forms
+}
+
+/// Returns the error for a form whose art could not be found: an unknown form, with the
+/// forms the Pokémon has and the closest one, or else a missing art.
+///
+/// # Parameters
+/// - `pokemon`: The Pokémon the form was asked for.
+/// - `name`: The slug the art files are named after.
+/// - `form`: The canonical form asked for.
+/// - `slug`: The slug of the art that could not be found.
+/// - `pokemon_db`: The entire Pokémon database, used to tell forms and other Pokémon apart.
+/// - `assets`: The assets the art is looked up in.
+///
+/// # Returns
+/// - `Error`: `Error::InvalidForm` if the Pokémon has no art for the form at all,
+///   `Error::InvalidPokemon` otherwise (e.g. the form only lacks shiny art).
+fn invalid_form_or_pokemon(
+    pokemon: &Pokemon,
+    name: &str,
+    form: &str,
+    slug: String,
+    pokemon_db: &PokemonDb,
+    assets: &Assets,
+) -> Error {
+    let forms = available_forms(pokemon, name, pokemon_db, assets, false);
+    if forms.is_empty() || forms.iter().any(|f| f == form) {
+        return Error::InvalidPokemon(slug);
+    }
+    let reason = match forms::closest_form(form, &forms) {
+        Some(closest) => format!("did you mean `{closest}`? It has [{}]", forms.join(", ")),
+        None => format!("should be one of [{}]", forms.join(", ")),
+    };
+    Error::InvalidForm(name.to_string(), form.to_string(), reason)
+}
+
+/// Prints the forms of a Pokémon that have art, and whether they have shiny art.
+///
+/// # Parameters
+/// - `pokemon`: The Pokémon to list the forms of.
+/// - `slug`: The slug the art files are named after.
+/// - `pokemon_db`: The entire Pokémon database.
+/// - `assets`: The assets the art is looked up in.
+fn list_forms(pokemon: &Pokemon, slug: &str, pokemon_db: &PokemonDb, assets: &Assets) {
+    let shiny_forms = available_forms(pokemon, slug, pokemon_db, assets, true);
+    let mut forms = available_forms(pokemon, slug, pokemon_db, assets, false);
+    forms.extend(shiny_forms.iter().cloned());
+    forms.sort_by(|a, b| (a != "regular", a).cmp(&(b != "regular", b)));
+    forms.dedup();
+
+    for form in forms {
+        let shiny = if shiny_forms.contains(&form) {
+            "yes"
+        } else {
+            "no"
+        };
+        println!("{: <15} shiny: {}", form, shiny);
+    }
+}
+
+/// Resolves the Pokémon asked for by name: slugs and forms are matched ignoring case,
+/// and an alias from the configuration stands for a Pokémon, form and shininess, the
+/// options given along still applying.
+///
+/// # Parameters
+/// - `name`: A reference to the `cli::Name` struct as given on the command line.
+/// - `pokemon_db`: The entire Pokémon database, as Pokémon names take precedence over aliases.
+/// - `config`: A reference to the `Config` struct containing the aliases.
+///
+/// # Returns
+/// - `cli::Name`: The options with the lowercase slug and form of the Pokémon to show.
+fn resolve_name(name: &cli::Name, pokemon_db: &PokemonDb, config: &Config) -> cli::Name {
+    let slug = name.name.to_lowercase();
+    let form = name.form.to_lowercase();
+    let alias = config
+        .aliases
+        .iter()
+        .find(|(alias, _)| alias.to_lowercase() == slug)
+        .map(|(_, alias)| alias);
+
+    match alias {
+        Some(alias) if pokemon_db.get(&slug).is_none() => cli::Name {
+            name: alias.slug.clone(),
+            form: match form.as_str() {
+                "regular" => alias.form.clone(),
+                _ => form,
+            },
+            shiny: name.shiny || alias.shiny,
+            ..name.clone()
+        },
+        _ => cli::Name {
+            name: slug,
+            form,
+            ..name.clone()
+        },
+    }
+}
+
+/// Writes the name of a Pokémon in the header, padded to the left padding of the art.
+///
+/// The padding is measured by display width, as CJK characters take two columns.
+///
+/// # Parameters
+/// - `out`: The writer the header is written to.
+/// - `pokemon_name`: The localized name of the Pokémon.
+/// - `url`: The URL the name links to, if it is written as a hyperlink.
+/// - `padding_left`: The number of columns the name is padded to.
+fn write_padded_name(
+    out: &mut dyn Write,
+    pokemon_name: &str,
+    url: Option<&str>,
+    padding_left: usize,
+) -> io::Result<()> {
+    let padding = padding_left.saturating_sub(ascii::visible_width(pokemon_name));
+    match url {
+        Some(url) => write!(out, "{}", ascii::hyperlink(pokemon_name, url))?,
+        None => write!(out, "{pokemon_name}")?,
+    }
+    write!(out, "{}", " ".repeat(padding))
+}
+
+/// Displays information about a Pokémon based on its name and specified form.
+///
+/// This function searches for a Pokémon in the database using its slug (name).
+/// If found, it retrieves and displays the Pokémon's ASCII art, potential
+/// descriptions, and stats if requested. The function handles shiny variants
+/// and different forms of the Pokémon, as well as optional titles and game
+/// information based on user input.
 ///
 /// # Parameters
 /// - `name`: A reference to the `cli::Name` struct containing the Pokémon's name,
 ///   form, shiny status, and other display preferences.
-/// - `pokemon_db`: A vector of `Pokemon` objects representing the entire Pokémon database.
+/// - `pokemon_db`: The entire Pokémon database, indexed by slug.
 /// - `config`: A reference to the `Config` struct containing configuration settings such as language.
+/// - `rng`: The random number generator picking the random form and Pokédex entry.
 ///
 /// # Returns
 /// - `Result<(), Error>`: Returns `Ok(())` if the Pokémon is successfully found and displayed,
 ///   or an `Error` if the Pokémon is not found, the language is invalid, or other issues occur.
 fn show_pokemon_by_name(
     name: &cli::Name,
-    pokemon_db: Vec<Pokemon>,
+    pokemon_db: &PokemonDb,
     config: &Config,
+    rng: &mut impl Rng,
 ) -> Result<(), Error> {
-    let base_name = name.name.split('-').next().unwrap_or(&name.name);
+    let assets = Assets::from_config(config);
+
+    let name = &resolve_name(name, pokemon_db, config);
+
+    match find_base_pokemon(&name.name, pokemon_db) {
+        Some(pokemon) if name.list_forms => {
+            list_forms(pokemon, &name.name, pokemon_db, &assets);
+            Ok(())
+        }
+        Some(pokemon) if name.form == "all" => {
+            // Render every form through the single form path, one after the other
+            let shiny_only = name.shiny && !name.shiny_fallback;
+            let forms = available_forms(pokemon, &name.name, pokemon_db, &assets, shiny_only);
+            for (i, form) in forms.into_iter().enumerate() {
+                if i > 0 {
+                    println!();
+                }
+                let single = cli::Name {
+                    form,
+                    animate: None,
+                    ..name.clone()
+                };
+                show_pokemon_by_name(&single, pokemon_db, config, rng)?;
+            }
+            Ok(())
+        }
+        Some(pokemon) if name.form == "shiny-only" => {
+            // Every form with shiny art, rendered shiny
+            let forms = available_forms(pokemon, &name.name, pokemon_db, &assets, true);
+            let skipped = available_forms(pokemon, &name.name, pokemon_db, &assets, false)
+                .iter()
+                .filter(|form| !forms.contains(form))
+                .count();
+            for (i, form) in forms.into_iter().enumerate() {
+                if i > 0 {
+                    println!();
+                }
+                let single = cli::Name {
+                    form,
+                    shiny: true,
+                    animate: None,
+                    ..name.clone()
+                };
+                show_pokemon_by_name(&single, pokemon_db, config, rng)?;
+            }
+            if skipped > 0 {
+                eprintln!("Skipped {skipped} form(s) without shiny art");
+            }
+            Ok(())
+        }
+        Some(_) if name.form.contains(',') => {
+            // Render the listed forms in order through the single form path
+            let mut shown = 0;
+            for form in name
+                .form
+                .split(',')
+                .map(str::trim)
+                .filter(|f| !f.is_empty())
+            {
+                let form = forms::canonical_form(form);
+                let slug = match form {
+                    "regular" => name.name.clone(),
+                    form => format!("{}-{}", name.name, form),
+                };
+                let has_art = !assets.get_frames(&art_path(&slug, name.shiny)).is_empty()
+                    || name.shiny_fallback
+                        && !assets.get_frames(&art_path(&slug, false)).is_empty();
+                if !has_art {
+                    eprintln!("Skipping the {form} form of {}, it has no art", name.name);
+                    continue;
+                }
 
-    match pokemon_db.iter().find(|p| p.slug == base_name) {
+                if shown > 0 {
+                    println!();
+                }
+                let single = cli::Name {
+                    form: form.to_string(),
+                    animate: None,
+                    ..name.clone()
+                };
+                show_pokemon_by_name(&single, pokemon_db, config, rng)?;
+                shown += 1;
+            }
+            if shown == 0 {
+                return Err(Error::InvalidPokemon(format!(
+                    "{} ({})",
+                    name.name, name.form
+                )));
+            }
+            Ok(())
+        }
         Some(pokemon) => {
-            let slug = name.name.clone();
+            let form = match name.form.as_str() {
+                // Any form with art, uniformly
+                "random" => available_forms(
+                    pokemon,
+                    &name.name,
+                    pokemon_db,
+                    &assets,
+                    name.shiny && !name.shiny_fallback,
+                )
+                .choose(rng)
+                .cloned()
+                .ok_or_else(|| Error::InvalidPokemon(name.name.clone()))?,
+                form => forms::canonical_form(form).to_string(),
+            };
+            let form = form.as_str();
+            let slug = match form {
+                "regular" => name.name.clone(),
+                form => format!("{}-{}", name.name, form),
+            };
+
+            let art_path = art_path(&slug, name.shiny);
+
+            let mut frames = assets.get_frames(&art_path);
+            // Many forms only have regular art
+            if frames.is_empty() && name.shiny && name.shiny_fallback {
+                frames = assets.get_frames(&self::art_path(&slug, false));
+                if !frames.is_empty() {
+                    eprintln!("No shiny sprite for {slug}, showing the regular one");
+                }
+            }
+            if frames.is_empty() {
+                return Err(invalid_form_or_pokemon(
+                    pokemon, &name.name, form, slug, pokemon_db, &assets,
+                ));
+            }
+            let arts: Vec<String> = frames
+                .iter()
+                .map(|frame| {
+                    let art = std::str::from_utf8(frame).expect("Invalid UTF-8 in pokemon art");
+                    let mut art = art.to_string();
+                    if name.mirror {
+                        art = ascii::mirror_art(&art);
+                    }
+                    if let Some(palette) = name.palette {
+                        art = ascii::recolor_art(&art, palette);
+                    }
+                    if name.ascii {
+                        art = ascii::ascii_only_art(&art);
+                    } else if name.color256 || ascii::limited_color_terminal() {
+                        art = ascii::downsample_to_256(&art);
+                    }
+                    art
+                })
+                .collect();
+            // Animated art is shown still on its first frame unless --animate is given
+            let art = arts[0].as_str();
 
-            let art_path = if name.shiny {
-                format!("colorscripts/shiny/{}", slug)
+            // Chosen first, so an ambiguous game fails before anything is printed
+            let (desc_game, mut desc_lines) = if name.info {
+                match description::select_description(
+                    pokemon,
+                    config,
+                    &name.game_info,
+                    name.desc_index,
+                    rng,
+                )? {
+                    Some((game, lines)) => (Some(game), lines),
+                    None => (None, Vec::new()),
+                }
             } else {
-                format!("colorscripts/regular/{}", slug)
+                (None, Vec::new())
             };
+            // Keep long entries from towering over the art
+            let truncated_line;
+            if let Some(max_lines) = name.desc_max_lines {
+                if desc_lines.len() > max_lines as usize {
+                    desc_lines.truncate(max_lines as usize);
+                    if let Some(last) = desc_lines.pop() {
+                        truncated_line = format!("{} …", last.trim_end());
+                        desc_lines.push(&truncated_line);
+                    }
+                }
+            }
+            // Tell which game the entry comes from, so screenshots are sourced
+            let source_line;
+            if let Some(game) = desc_game.filter(|_| !name.no_desc_source) {
+                source_line = format!("({})", description::game_title(game));
+                desc_lines.push(&source_line);
+            }
 
-            let art = Asset::get(&art_path)
-                .unwrap_or_else(|| panic!("Could not read pokemon art of '{}'", slug))
-                .data;
-            let art = std::str::from_utf8(&art).expect("Invalid UTF-8 in pokemon art");
+            // Appended to, as --count and --form all render several Pokémon in a row
+            let mut out: Box<dyn Write> = match &name.output_file {
+                Some(path) => Box::new(
+                    fs::OpenOptions::new()
+                        .create(true)
+                        .append(true)
+                        .open(path)?,
+                ),
+                None => Box::new(io::stdout().lock()),
+            };
 
             if !name.no_title {
-                let pokemon_name = match pokemon.name.get(&config.language) {
-                    Some(n) => n,
-                    None => return Err(Error::InvalidLanguage(config.language.clone())),
-                };
-                print!("{: <1$}", pokemon_name, name.padding_left);
-                match name.form.as_str() {
-                    "regular" => println!(),
-                    other => println!(" ({other})"),
+                let pokemon_name = pokemon.localized_name(&config.languages())?;
+                // Entries without a number (e.g. from an older --db) keep the plain name
+                if (name.show_dex || config.show_dex) && pokemon.idx > 0 {
+                    write!(out, "#{:04} ", pokemon.idx)?;
                 }
-            }
-            let desc_lines: Vec<&str> = if name.info {
-                if let Some(game_descriptions) = pokemon.desc.get(&config.language) {
-                    if name.game_info.is_empty() {
-                        let games: Vec<&String> = game_descriptions.keys().collect();
-                        if let Some(random_game) = games.choose(&mut rand::rng()) {
-                            game_descriptions
-                                .get(*random_game)
-                                .map(|desc| desc.lines().collect())
-                                .unwrap_or_default()
-                        } else {
-                            Vec::new()
-                        }
+                // Links are escape sequences too, only for a terminal showing colors
+                let link = config.link_names
+                    && name.info
+                    && name.output_file.is_none()
+                    && io::stdout().is_terminal()
+                    && ascii::color_enabled();
+                let url = link.then(|| config.link_url.replace("{slug}", &pokemon.slug));
+                write_padded_name(&mut out, pokemon_name, url.as_deref(), name.padding_left)?;
+                if form != "regular" {
+                    write!(out, " ({form})")?;
+                }
+                // Spelled out when colors are off, as the art alone no longer tells
+                if name.shiny {
+                    let marker = if ascii::color_enabled() {
+                        "✨"
                     } else {
-                        game_descriptions
-                            .get(&name.game_info)
-                            .map(|desc| desc.lines().collect())
-                            .unwrap_or_else(|| description::get_random_description(pokemon, config))
+                        "[shiny]"
+                    };
+                    write!(out, " {marker}")?;
+                }
+                if name.show_types || name.info {
+                    if let Some(chip) = types::format_types(&pokemon.types, ascii::color_enabled())
+                    {
+                        write!(out, " {chip}")?;
+                    } else if name.show_types {
+                        eprintln!(
+                            "No types for {}, regenerate the data with utility/pokemon_scraping.py",
+                            pokemon.slug
+                        );
                     }
-                } else {
-                    description::get_random_description(pokemon, config)
                 }
-            } else {
-                Vec::new()
-            };
-            if name.info {
-                if name.under {
-                    ascii::draw_pokemon_art_under(
+                writeln!(out)?;
+            }
+            // Fall back to the under layout if the description would wrap around
+            let under = name.under
+                || ascii::terminal_width().is_some_and(|columns| {
+                    let frame_width = if name.frame { 4 } else { 0 };
+                    !ascii::fits_beside(
                         art,
-                        desc_lines,
+                        &desc_lines,
                         name.padding_left,
-                        &config.language,
-                    );
+                        columns.saturating_sub(frame_width),
+                    )
+                });
+
+            let missing_notice = description::missing_notice(pokemon, config);
+            // Validated when loading the config; without it the terminal's default applies
+            let desc_color = if name.no_desc_color {
+                String::new()
+            } else {
+                config.description_color.escape().unwrap_or_default()
+            };
+            // Checked by the argument parser
+            let background = name
+                .bg
+                .as_ref()
+                .and_then(|bg| bg.background_escape())
+                .unwrap_or_default();
+
+            let options = ascii::RenderOptions {
+                layout: match (name.info, under) {
+                    (false, _) => ascii::Layout::ArtOnly,
+                    (true, false) => ascii::Layout::Beside,
+                    (true, true) => ascii::Layout::Under,
+                },
+                desc_lines: &desc_lines,
+                padding_left: name.padding_left,
+                padding_top: name.padding_top,
+                missing_notice: &missing_notice,
+                quiet: name.quiet,
+                desc_color: &desc_color,
+                background: &background,
+            };
+
+            // Only record encounters on request so casual viewing leaves the dex alone
+            let record_encounter = || -> Result<(), Error> {
+                if name.track || config.track_encounters {
+                    let pokedex_path = get_pokedex_path()?;
+                    track_encounter(
+                        pokedex_path.to_str().expect("None"),
+                        &pokemon.slug,
+                        name.unique,
+                    )?;
+                }
+                Ok(())
+            };
+
+            // --radar draws the stats as a chart instead of the grid
+            let write_stats = |out: &mut dyn Write| -> io::Result<()> {
+                let (language, tiers) = (&config.language, &config.stat_tiers);
+                if name.radar {
+                    stats::display_pokemon_radar(out, pokemon, form, language, tiers)
+                } else if name.stats {
+                    stats::display_pokemon_stats(out, pokemon, form, language, tiers)
                 } else {
-                    ascii::draw_pokemon_art(art, desc_lines, name.padding_left, &config.language);
+                    Ok(())
                 }
-            } else {
-                ascii::print_ascii_art(art, name.padding_left);
+            };
+
+            let animate = name
+                .animate
+                .filter(|_| arts.len() > 1 && name.output_file.is_none())
+                .filter(|_| io::stdout().is_terminal());
+            if let Some(frame_delay) = animate {
+                // Each frame is redrawn whole, stats included, in place of the previous one
+                let mut stats_text = Vec::new();
+                write_stats(&mut stats_text)?;
+                let stats_text = String::from_utf8_lossy(&stats_text);
+                let rendered: Vec<String> = arts
+                    .iter()
+                    .map(|art| {
+                        let rendered = ascii::render(art, &options);
+                        let rendered = if name.frame {
+                            ascii::frame(&rendered)
+                        } else {
+                            rendered
+                        };
+                        format!("{rendered}{stats_text}")
+                    })
+                    .collect();
+
+                // The animation only ends with the process, so the encounter is recorded first
+                record_encounter()?;
+                ascii::animate(&mut out, &rendered, Duration::from_millis(frame_delay))?;
+                return Ok(());
             }
 
-            if name.stats {
-                stats::display_pokemon_stats(pokemon);
+            // Animating only makes sense on the terminal
+            let delay = name.delay.filter(|_| name.output_file.is_none());
+            match (name.frame, delay) {
+                // The whole output is needed up front to size the box
+                (true, delay) => {
+                    let framed = ascii::frame(&ascii::render(art, &options));
+                    let delay = Duration::from_millis(delay.unwrap_or(0));
+                    ascii::reveal_line_by_line(&mut out, &framed, delay)?;
+                }
+                (false, Some(delay)) => {
+                    let rendered = ascii::render(art, &options);
+                    ascii::reveal_line_by_line(&mut out, &rendered, Duration::from_millis(delay))?;
+                }
+                (false, None) => ascii::render_to(&mut out, art, &options)?,
             }
 
-            Ok(())
+            write_stats(&mut out)?;
+            drop(out);
+
+            record_encounter()
         }
         None => Err(Error::InvalidPokemon(name.name.clone())),
     }
 }
 
-fn get_pokedex_path() -> Result<PathBuf, io::Error> {
-    if let Some(mut path) = dirs::home_dir() {
-        // Attempt to create .config directory
-        path.push(".config");
-        if let Err(e) = fs::create_dir_all(&path) {
-            eprintln!("Failed to create .config directory: {}", e);
-            return Err(e);
+/// Reads or changes a setting of the configuration file.
+///
+/// `get` prints the value in effect, environment overrides included, while `set`
+/// changes the value stored in the file only.
+///
+/// # Parameters
+/// - `command`: The `config` subcommand to run.
+///
+/// # Returns
+/// - `Result<(), Error>`: Returns `Ok(())` on success, or `Error::Configuration` for an
+///   unknown setting or an invalid value.
+fn run_config_command(command: &cli::ConfigCommands) -> Result<(), Error> {
+    match command {
+        cli::ConfigCommands::Get { key } => println!("{}", Config::load()?.get(key)?),
+        cli::ConfigCommands::Set { key, value } => {
+            let mut config = Config::load_file()?;
+            config.set(key, value)?;
+            config.save()?;
         }
+    }
+    Ok(())
+}
+
+/// Turns on the `--info`, `--shiny` and `--under` flags the configuration enables by
+/// default, unless turned off on the command line with `--no-info`, `--no-shiny` or
+/// `--no-under`. The command line takes precedence over the configuration, whose
+/// defaults take precedence over the flags being off.
+///
+/// # Parameters
+/// - `command`: The parsed command, changed in place when it is name, random or daily.
+/// - `config`: The loaded configuration, with the `default_*` settings.
+fn apply_flag_defaults(command: &mut cli::Commands, config: &Config) {
+    let flags = match command {
+        cli::Commands::Name(name) => [
+            (&mut name.info, name.no_info),
+            (&mut name.shiny, name.no_shiny),
+            (&mut name.under, name.no_under),
+        ],
+        cli::Commands::Random(random) | cli::Commands::Daily(cli::Daily { random, .. }) => [
+            (&mut random.info, random.no_info),
+            (&mut random.shiny, random.no_shiny),
+            (&mut random.under, random.no_under),
+        ],
+        _ => return,
+    };
+    let defaults = [
+        config.default_info,
+        config.default_shiny,
+        config.default_under,
+    ];
+    for ((flag, turned_off), default) in flags.into_iter().zip(defaults) {
+        *flag |= default && !turned_off;
+    }
+}
 
-        // Attempt to create kingler directory
-        path.push("kingler");
-        if let Err(e) = fs::create_dir_all(&path) {
-            eprintln!("Failed to create kingler directory: {}", e);
-            return Err(e);
+/// Checks the Pokémon data for what showing a Pokémon relies on: regular and shiny art,
+/// stats and an English name, printing the slugs missing each.
+///
+/// # Parameters
+/// - `pokemon_db`: The loaded Pokémon database, from `--db` or the assets.
+/// - `config`: The loaded configuration, for the assets directory.
+///
+/// # Returns
+/// - `Result<(), Error>`: Returns `Ok(())` if nothing is missing, or `Error::DataIntegrity`
+///   with the number of problems found.
+fn check_data(pokemon_db: &PokemonDb, config: &Config) -> Result<(), Error> {
+    let assets = Assets::from_config(config);
+    let has_art =
+        |p: &Pokemon, shiny: bool| !assets.get_frames(&art_path(&p.slug, shiny)).is_empty();
+    let missing = |check: &dyn Fn(&Pokemon) -> bool| -> Vec<&str> {
+        pokemon_db
+            .iter()
+            .filter(|p| !check(p))
+            .map(|p| p.slug.as_str())
+            .collect()
+    };
+    let checks = [
+        ("regular art", missing(&|p| has_art(p, false))),
+        ("shiny art", missing(&|p| has_art(p, true))),
+        ("stats", missing(&|p| !p.stats.is_empty())),
+        ("English name", missing(&|p| p.name.contains_key("en"))),
+    ];
+
+    println!("Checked {} Pokémon", pokemon_db.len());
+    let mut problems = 0;
+    for (what, missing) in checks {
+        if missing.is_empty() {
+            println!("Missing {what}: none");
+        } else {
+            println!("Missing {what} ({}): {}", missing.len(), missing.join(", "));
         }
+        problems += missing.len();
+    }
+
+    if problems > 0 {
+        return Err(Error::DataIntegrity(problems));
+    }
+    Ok(())
+}
 
-        // Add the file name for the Pokedex
-        path.push("pokedex.json");
+/// Prints the resolved locations of the files kingler reads and writes, and
+/// whether each exists.
+///
+/// # Parameters
+/// - `config`: The loaded configuration, for the shiny log path and assets dir.
+/// - `pokedex_path`: The path of the Pokédex tracker.
+fn print_paths(config: &Config, pokedex_path: &Path) {
+    let mut paths = vec![
+        ("config", Config::path()),
+        ("shiny log", PathBuf::from(&config.shiny_log_path)),
+        ("pokedex tracker", pokedex_path.to_path_buf()),
+        (
+            "hunt counters",
+            config::data_file_path_or_temp("hunts.json"),
+        ),
+    ];
+    if let Some(assets_dir) = &config.assets_dir {
+        paths.push(("assets dir", PathBuf::from(assets_dir)));
+    }
 
-        Ok(path)
-    } else {
-        eprintln!("Home directory could not be determined. Defaulting to local path.");
-        Ok(PathBuf::from("pokedex.json"))
+    for (label, path) in paths {
+        let status = if path.exists() { "exists" } else { "missing" };
+        println!(
+            "{:<16} {} ({})",
+            format!("{label}:"),
+            path.display(),
+            status
+        );
     }
 }
 
-/// Ensures that the `.config/kingler/pokedex.json` file exists and is initialized
+fn get_pokedex_path() -> Result<PathBuf, io::Error> {
+    Ok(config::data_file_path_or_temp("pokedex.json"))
+}
+
+/// Ensures that the `pokedex.json` file in the config directory exists and is initialized
 /// with an empty `EncounteredPokemonTracker` structure if not already present.
 ///
 /// # Parameters
@@ -353,11 +2012,57 @@ fn initialize_tracker(tracker_path: &PathBuf) -> Result<(), Error> {
     Ok(())
 }
 
-fn main() -> Result<(), Error> {
+fn main() -> ExitCode {
+    let args = cli::Cli::parse();
+    // Scripts reading JSON output get the errors as JSON too
+    let json = args.json_output();
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            if json {
+                let error = serde_json::json!({ "error": e.to_string(), "kind": e.kind() });
+                eprintln!("{error}");
+            } else {
+                eprintln!("Error: {e}");
+            }
+            ExitCode::from(e.exit_code())
+        }
+    }
+}
+
+fn run(mut args: cli::Cli) -> Result<(), Error> {
+    // Settings are changed before loading, so an invalid value in the file can be fixed
+    if let cli::Commands::Config(config) = &args.command {
+        return run_config_command(&config.command);
+    }
     let config = Config::load()?;
-    let pokemon_db = Asset::get("pokemon.json").expect("Could not read pokemon db file");
+    apply_flag_defaults(&mut args.command, &config);
+    // A database given with --db replaces the embedded one and the assets dir's
+    let pokemon_db = match &args.db {
+        Some(path) => Cow::Owned(fs::read(path).map_err(|e| {
+            Error::Io(format!(
+                "Failed to read pokemon db {}: {}",
+                path.display(),
+                e
+            ))
+        })?),
+        None => Assets::from_config(&config)
+            .pokemon_db()
+            .expect("Could not read pokemon db file"),
+    };
     let pokemon = load_pokemon(&pokemon_db)?;
-    let args = cli::Cli::parse();
+    config.check_aliases(&pokemon);
+    if args.no_color {
+        ascii::disable_color();
+    }
+    if let Some(width) = args.width {
+        ascii::set_terminal_width(width.into());
+    }
+    // A fixed seed makes every random choice reproducible
+    let mut rng = match args.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_rng(&mut rand::rng()),
+    };
     // Construct the tracker path starting from the user's home directory
     let pokedex_path = get_pokedex_path()?;
 
@@ -365,14 +2070,340 @@ fn main() -> Result<(), Error> {
     initialize_tracker(&pokedex_path)?;
     match args.command {
         cli::Commands::Init(shell) => cli::print_completions(shell.shell, &mut cli::build()),
-        cli::Commands::List => pokemon::list_pokemon_names(pokemon),
-        cli::Commands::Name(name) => show_pokemon_by_name(&name, pokemon, &config)?,
-        cli::Commands::Random(random) => show_random_pokemon(&random, pokemon, &config)?,
-        cli::Commands::ShowShiny => display_shiny_log(&config.shiny_log_path)?,
-        cli::Commands::ShowCompletion => {
-            show_completion_status(pokedex_path.to_str().expect("None"), 1025)?
+        cli::Commands::Stats(ranking) => {
+            let generations = parse_generations(&ranking.generations)?;
+            let type_name = ranking.type_name.as_deref();
+            if ranking.average {
+                stats::display_stat_averages(
+                    &pokemon,
+                    &generations,
+                    type_name,
+                    &config.language,
+                    &config.stat_tiers,
+                )?
+            } else {
+                stats::display_stat_ranking(
+                    &pokemon,
+                    &ranking.sort,
+                    &generations,
+                    type_name,
+                    ranking.top,
+                )?
+            }
+        }
+        cli::Commands::Export(export) => {
+            let pokemon = filter_dex_range(pokemon, export.dex.as_deref())?;
+            export::export_pokemon(collapse_forms(pokemon, export.species_only), export.format)?
+        }
+        cli::Commands::List(list) => {
+            let pokemon = filter_dex_range(pokemon, list.dex.as_deref())?;
+            let pokemon = collapse_forms(pokemon, list.species_only);
+            with_pager(list.page, |out| {
+                if list.count {
+                    pokemon::count_pokemon(out, &pokemon, list.by_gen)
+                } else if list.detailed {
+                    pokemon::list_pokemon_details(out, &pokemon)
+                } else {
+                    pokemon::list_pokemon_names(out, &pokemon)
+                }
+            })?
+        }
+        cli::Commands::Name(name) => {
+            create_output_file(name.output_file.as_ref())?;
+            show_pokemon_by_name(&name, &pokemon, &config, &mut rng)?
         }
+        cli::Commands::Random(random) => {
+            create_output_file(random.output_file.as_ref())?;
+            match random.repeat {
+                Some(seconds) => repeat_every(seconds, || {
+                    show_random_pokemon(&random, &pokemon, &config, &mut rng)
+                })?,
+                None => show_random_pokemon(&random, &pokemon, &config, &mut rng)?,
+            }
+        }
+        cli::Commands::Daily(daily) => {
+            create_output_file(daily.random.output_file.as_ref())?;
+            // Repeating picks up the next Pokémon once the period changes
+            match daily.random.repeat {
+                Some(seconds) => {
+                    repeat_every(seconds, || show_daily_pokemon(&daily, &pokemon, &config))?
+                }
+                None => show_daily_pokemon(&daily, &pokemon, &config)?,
+            }
+        }
+        cli::Commands::Version(version) => {
+            println!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+            if version.data {
+                pokemon::describe_dataset(&mut io::stdout().lock(), &pokemon)?;
+            }
+        }
+        cli::Commands::Paths => print_paths(&config, &pokedex_path),
+        cli::Commands::Doctor => check_data(&pokemon, &config)?,
+        cli::Commands::Config(_) => unreachable!("handled before loading the configuration"),
+        cli::Commands::WhosThat(whosthat) => {
+            play_whos_that_pokemon(&whosthat, &pokemon, &config, &mut rng)?
+        }
+        cli::Commands::Sprite(sprite) => print_sprite(&sprite, &config)?,
+        cli::Commands::Battle(battle) => show_battle(&battle, &pokemon, &config)?,
+        cli::Commands::Describe(describe) => {
+            // A language given on the command line takes precedence for this run
+            let config = match &describe.lang {
+                Some(lang) => {
+                    Language::from_code(lang)?;
+                    Config {
+                        language: lang.clone(),
+                        ..config
+                    }
+                }
+                None => config,
+            };
+            describe_pokemon(&describe, &pokemon, &config, &mut rng)?
+        }
+        cli::Commands::Catch(catch) => log_shiny_catch(&catch, &pokemon, &config.shiny_log_path)?,
+        cli::Commands::Odds(odds) => show_shiny_odds(&odds, &config),
+        cli::Commands::Hunt(hunt) => count_hunt_encounter(
+            &hunt,
+            &pokemon,
+            &config::data_file_path_or_temp("hunts.json"),
+        )?,
+        cli::Commands::Today => display_today(
+            &config.shiny_log_path,
+            &config::data_file_path_or_temp("hunts.json"),
+        )?,
+        cli::Commands::Shiny(shiny) => match shiny.command {
+            cli::ShinyCommands::Repair => {
+                let (kept, dropped) = shiny_hunting::repair_shiny_log(&config.shiny_log_path)?;
+                println!(
+                    "Kept {kept} entries and dropped {dropped} unreadable ones from {}",
+                    config.shiny_log_path
+                );
+            }
+            cli::ShinyCommands::Export { format } => {
+                // Nothing caught yet is an empty table
+                let entries = match shiny_hunting::load_shiny_log(&config.shiny_log_path) {
+                    Ok(entries) => entries,
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+                    Err(e) => return Err(e.into()),
+                };
+                export::export_shiny_log(&entries, format)?
+            }
+        },
+        cli::Commands::ShowShiny(show_shiny) => {
+            display_shiny_log(&config.shiny_log_path, show_shiny.since)?
+        }
+        cli::Commands::ShowCompletion(completion) => show_completion_status(
+            pokedex_path.to_str().expect("None"),
+            &pokemon,
+            completion.json,
+        )?,
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pokemon_db() -> PokemonDb {
+        let data = r#"[
+            {"idx": 25, "slug": "pikachu", "gen": 1, "name": {"en": "Pikachu"}, "desc": {}, "forms": ["regular"]},
+            {"idx": 37, "slug": "vulpix", "gen": 1, "name": {"en": "Vulpix"}, "desc": {}, "forms": ["regular", "alola"]},
+            {"idx": 3, "slug": "venusaur", "gen": 1, "name": {"en": "Venusaur"}, "desc": {}, "forms": ["regular", "mega", "gmax"]},
+            {"idx": 6, "slug": "charizard", "gen": 1, "name": {"en": "Charizard"}, "desc": {}, "forms": ["regular", "mega-x", "mega-y", "gmax"]},
+            {"idx": 29, "slug": "nidoran-f", "gen": 1, "name": {"en": "Nidoran♀"}, "desc": {}, "forms": ["regular"]},
+            {"idx": 32, "slug": "nidoran-m", "gen": 1, "name": {"en": "Nidoran♂"}, "desc": {}, "forms": ["regular"]},
+            {"idx": 83, "slug": "farfetchd", "gen": 1, "name": {"en": "Farfetch'd"}, "desc": {}, "forms": ["regular", "galar"]},
+            {"idx": 474, "slug": "porygon-z", "gen": 4, "name": {"en": "Porygon-Z"}, "desc": {}, "forms": ["regular"]},
+            {"idx": 669, "slug": "flabebe", "gen": 6, "name": {"en": "Flabébé"}, "desc": {}, "forms": ["regular"]},
+            {"idx": 772, "slug": "type-null", "gen": 7, "name": {"en": "Type: Null"}, "desc": {}, "forms": ["regular"]},
+            {"idx": 122, "slug": "mr-mime", "gen": 1, "name": {"en": "Mr. Mime"}, "desc": {}, "forms": ["regular", "galar"]},
+            {"idx": 250, "slug": "ho-oh", "gen": 2, "name": {"en": "Ho-Oh"}, "desc": {}, "forms": ["regular"]}
+        ]"#;
+        pokemon::load_pokemon(data.as_bytes()).unwrap()
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("kingler-{}-{name}.json", std::process::id()))
+    }
+
+    fn generations_error(generations: &str) -> String {
+        match parse_generations(generations) {
+            Err(Error::InvalidGeneration(arg, reason)) if arg == generations => reason,
+            other => panic!("expected an invalid generation, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_generations_accepts_ranges_and_lists() {
+        assert_eq!(parse_generations("1-3").unwrap(), [1..=3]);
+        assert_eq!(parse_generations("4-4").unwrap(), [4..=4]);
+        assert_eq!(parse_generations("1, 3,6").unwrap(), [1..=1, 3..=3, 6..=6]);
+    }
+
+    #[test]
+    fn parse_generations_rejects_reversed_ranges() {
+        assert!(generations_error("9-1").contains("reversed"));
+    }
+
+    #[test]
+    fn parse_generations_rejects_out_of_range_numbers() {
+        for generations in ["0-3", "1-10", "0", "1,10", "x", "-1"] {
+            assert!(generations_error(generations).contains("between 1 and 9"));
+        }
+    }
+
+    #[test]
+    fn find_base_pokemon_prefers_the_full_slug() {
+        let pokemon_db = pokemon_db();
+        let base = |name: &str| find_base_pokemon(name, &pokemon_db).map(|p| p.slug.as_str());
+
+        for slug in [
+            "nidoran-f",
+            "nidoran-m",
+            "mr-mime",
+            "farfetchd",
+            "flabebe",
+            "type-null",
+            "porygon-z",
+            "ho-oh",
+            "pikachu",
+        ] {
+            assert_eq!(base(slug), Some(slug));
+        }
+    }
+
+    #[test]
+    fn find_base_pokemon_drops_form_suffixes() {
+        let pokemon_db = pokemon_db();
+        let base = |name: &str| find_base_pokemon(name, &pokemon_db).map(|p| p.slug.as_str());
+
+        assert_eq!(base("vulpix-alola"), Some("vulpix"));
+        assert_eq!(base("mr-mime-galar"), Some("mr-mime"));
+        assert_eq!(base("farfetchd-galar"), Some("farfetchd"));
+        assert_eq!(base("nidoran-f-gmax"), Some("nidoran-f"));
+        assert_eq!(base("nidoran-m-gmax"), Some("nidoran-m"));
+        assert_eq!(base("type-null-mega"), Some("type-null"));
+        assert_eq!(base("porygon-z-mega-x"), Some("porygon-z"));
+        assert_eq!(base("flabebe-red"), Some("flabebe"));
+        assert_eq!(base("ho-oh-shiny-x"), Some("ho-oh"));
+        // A shared prefix is not a Pokémon of its own
+        assert_eq!(base("nidoran"), None);
+        assert_eq!(base("type"), None);
+        assert_eq!(base("mr"), None);
+        assert_eq!(base("missingno"), None);
+    }
+
+    /// Parses a `name` command line and resolves it, without aliases.
+    fn resolved(args: &[&str]) -> (String, String) {
+        let args = cli::Cli::try_parse_from([&["kingler", "name"], args].concat()).unwrap();
+        let cli::Commands::Name(name) = args.command else {
+            panic!("expected the name command");
+        };
+        let name = resolve_name(&name, &pokemon_db(), &Config::default());
+        (name.name, name.form)
+    }
+
+    #[test]
+    fn resolve_name_lowercases_names() {
+        let regular = |slug: &str| (slug.to_string(), "regular".to_string());
+
+        assert_eq!(resolved(&["Pikachu"]), regular("pikachu"));
+        assert_eq!(resolved(&["CHARIZARD"]), regular("charizard"));
+        assert_eq!(resolved(&["Mr-Mime"]), regular("mr-mime"));
+    }
+
+    #[test]
+    fn resolve_name_lowercases_forms() {
+        let form = |slug: &str, form: &str| (slug.to_string(), form.to_string());
+
+        assert_eq!(
+            resolved(&["Venusaur", "--form", "Mega"]),
+            form("venusaur", "mega")
+        );
+        assert_eq!(
+            resolved(&["CHARIZARD", "-f", "MEGA-X"]),
+            form("charizard", "mega-x")
+        );
+    }
+
+    fn padded_name(pokemon_name: &str, url: Option<&str>, padding_left: usize) -> String {
+        let mut out = Vec::new();
+        write_padded_name(&mut out, pokemon_name, url, padding_left).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn header_pads_names_by_display_width() {
+        assert_eq!(padded_name("Pikachu", None, 12), "Pikachu     ");
+        assert_eq!(padded_name("ピカチュウ", None, 12), "ピカチュウ  ");
+        assert_eq!(
+            ascii::visible_width(&padded_name("ピカチュウ", None, 12)),
+            12
+        );
+        assert_eq!(padded_name("ピカチュウ", None, 4), "ピカチュウ");
+    }
+
+    #[test]
+    fn header_pads_linked_names_by_their_text() {
+        let linked = padded_name(
+            "ピカチュウ",
+            Some("https://pokemondb.net/pokedex/pikachu"),
+            12,
+        );
+
+        assert!(linked.ends_with("\x1b]8;;\x1b\\  "));
+    }
+
+    #[test]
+    fn catch_normalizes_the_name() {
+        let log_path = temp_path("catch");
+        let log_path = log_path.to_str().unwrap();
+        for name in ["Pikachu", "VULPIX-Alola"] {
+            let catch = cli::Catch {
+                name: name.to_string(),
+                form: "regular".to_string(),
+                encounters: None,
+                details: String::new(),
+            };
+            log_shiny_catch(&catch, &pokemon_db(), log_path).unwrap();
+        }
+
+        let entries = shiny_hunting::load_shiny_log(log_path).unwrap();
+        let logged: Vec<(&str, &str)> = entries
+            .iter()
+            .map(|e| (e.pokemon_name.as_str(), e.form.as_str()))
+            .collect();
+        assert_eq!(logged, [("pikachu", "regular"), ("vulpix", "alola")]);
+        fs::remove_file(log_path).unwrap();
+    }
+
+    #[test]
+    fn hunt_normalizes_the_name() {
+        let counters_path = temp_path("hunt");
+        for name in ["Pikachu", "pikachu", "PIKACHU"] {
+            let hunt = cli::Hunt {
+                name: name.to_string(),
+                reset: false,
+            };
+            count_hunt_encounter(&hunt, &pokemon_db(), &counters_path).unwrap();
+        }
+
+        let counters = shiny_hunting::load_hunt_counters(&counters_path).unwrap();
+        assert_eq!(counters.get("pikachu"), Some(&3));
+        assert_eq!(counters.len(), 1);
+        fs::remove_file(counters_path).unwrap();
+    }
+
+    #[test]
+    fn catch_rejects_unknown_pokemon() {
+        let catch = cli::Catch {
+            name: "Missingno".to_string(),
+            form: "regular".to_string(),
+            encounters: None,
+            details: String::new(),
+        };
+        let error = log_shiny_catch(&catch, &pokemon_db(), "unused.json").unwrap_err();
+        assert!(matches!(error, Error::InvalidPokemon(name) if name == "Missingno"));
+    }
+}
@@ -1,17 +1,22 @@
-use rust_embed::EmbeddedFile;
-use serde::Deserialize;
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Deserializer};
 
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::{self, Read, Write};
+use std::ops::Deref;
 use std::str;
 
 use crate::error::Error;
+use crate::forms;
 
 /// Represents a Pokémon with various attributes including its slug, generation,
 /// names in different languages, descriptions, forms, and stats.
 ///
 /// # Fields
+/// - `idx`: The National Dex number of the Pokémon, 0 when the dataset has none.
 /// - `slug`: A unique identifier for the Pokémon, often used in URLs and APIs.
-/// - `gen`: The generation of the Pokémon, represented as an unsigned 8-bit integer.
+/// - `gen`: The generation of the Pokémon, represented as an unsigned 8-bit integer, 0 when unknown.
 /// - `name`: A hashmap containing the Pokémon's names in various languages,
 ///   where the key is the language code (e.g., "en" for English).
 /// - `desc`: A nested hashmap containing descriptions of the Pokémon for various games
@@ -19,37 +24,418 @@ use crate::error::Error;
 ///   name with the description as the value.
 /// - `forms`: A vector of strings representing the different forms the Pokémon can take (e.g.,
 ///   regular, mega, etc.).
-/// - `stats`: An optional hashmap that contains various stats of the Pokémon, where
-///   the key is the stat name (e.g., "attack") and the value is the stat value.
+/// - `stats`: A nested hashmap containing the stats of the Pokémon for each form. The outer
+///   key is the form (`regular` for the base form), and the inner key is the stat name
+///   (e.g., "attack") with the stat value as the value. Empty when the dataset has no stats.
+/// - `types`: A vector of the lowercase type names of the Pokémon (e.g., "grass", "poison"),
+///   empty when the dataset has no type data.
+/// - `category`: The category of the Pokémon, one of `normal`, `legendary` or `mythical`,
+///   empty when the dataset has no category data.
 #[derive(Clone, Debug, Deserialize)]
 pub struct Pokemon {
+    #[serde(default)]
+    pub idx: u16,
     pub slug: String,
+    #[serde(default)]
     pub gen: u8,
     pub name: HashMap<String, String>,
     pub desc: HashMap<String, HashMap<String, String>>,
-    pub stats: Option<HashMap<String, u32>>,
+    #[serde(default, deserialize_with = "deserialize_stats")]
+    pub stats: HashMap<String, HashMap<String, u32>>,
+    #[serde(default)]
+    pub forms: Vec<String>,
+    #[serde(default)]
+    pub types: Vec<String>,
+    #[serde(default)]
+    pub category: String,
 }
 
-/// Loads a list of Pokémon from an embedded JSON file.
+impl Pokemon {
+    /// Returns the name of the Pokémon in the first of several languages it has a name in.
+    ///
+    /// # Parameters
+    /// - `languages`: The language codes tried in order, e.g. `["de", "en"]`.
+    ///
+    /// # Returns
+    /// - `Ok(&str)`: The name in the first language that has one.
+    /// - `Err(Error)`: A `MissingName` error if none of the languages has a name.
+    pub fn localized_name(&self, languages: &[&str]) -> Result<&str, Error> {
+        languages
+            .iter()
+            .find_map(|language| self.name.get(*language))
+            .map(String::as_str)
+            .ok_or_else(|| Error::MissingName(self.slug.clone(), languages.join(", ")))
+    }
+
+    /// Returns the stats of a form of the Pokémon.
+    ///
+    /// Forms without stats of their own fall back to the stats of the regular form.
+    ///
+    /// # Parameters
+    /// - `form`: The form to look up, `regular` for the base form.
+    ///
+    /// # Returns
+    /// - `Option<&HashMap<String, u32>>`: The stats keyed by stat name, or `None` if
+    ///   neither the form nor the regular form has stats.
+    pub fn stats_for(&self, form: &str) -> Option<&HashMap<String, u32>> {
+        self.stats.get(form).or_else(|| self.stats.get("regular"))
+    }
+}
+
+/// The shapes of the `stats` field in the Pokémon database.
+///
+/// Older databases store a single map of stats per species, newer ones a map per form.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StatsShape {
+    PerForm(HashMap<String, HashMap<String, u32>>),
+    Single(HashMap<String, u32>),
+}
+
+/// Deserializes the `stats` field, accepting both the per-form and the older single-map
+/// shape, the latter being stored as the stats of the regular form.
+fn deserialize_stats<'de, D>(
+    deserializer: D,
+) -> Result<HashMap<String, HashMap<String, u32>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match Option::<StatsShape>::deserialize(deserializer)? {
+        Some(StatsShape::PerForm(stats)) => stats,
+        Some(StatsShape::Single(stats)) => HashMap::from([("regular".to_string(), stats)]),
+        None => HashMap::new(),
+    })
+}
+
+/// The two leading bytes of every gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Loads a list of Pokémon from a JSON file.
+///
+/// The file may be gzip-compressed; this is detected from its magic bytes and
+/// plain JSON is accepted as well.
 ///
 /// # Parameters
-/// - `pokemon_db`: A reference to an `EmbeddedFile` containing the Pokémon data in JSON format.
+/// - `pokemon_db`: The content of the file containing the Pokémon data in JSON format.
 ///
 /// # Returns
-/// - `Result<Vec<Pokemon>, Error>`: Returns a vector of `Pokemon` if the loading is successful,
-///   or an `Error` if there is an issue parsing the data.
-pub fn load_pokemon(pokemon_db: &EmbeddedFile) -> Result<Vec<Pokemon>, Error> {
-    let pokemon_json_str = str::from_utf8(&pokemon_db.data).expect("Invalid UTF-8 in pokemon db");
-    let pokemon: Vec<Pokemon> = serde_json::from_str(pokemon_json_str)?;
-    Ok(pokemon)
+/// - `Result<PokemonDb, Error>`: Returns the indexed Pokémon if the loading is successful,
+///   or an `Error` if there is an issue decompressing or parsing the data. Data that is not
+///   valid UTF-8, not an array of Pokémon, or an empty array is an `Error::PokemonDb`.
+pub fn load_pokemon(pokemon_db: &[u8]) -> Result<PokemonDb, Error> {
+    let data = decompress(pokemon_db)?;
+    let pokemon: Vec<Pokemon> = serde_json::from_slice(&data)?;
+    if pokemon.is_empty() {
+        return Err(Error::PokemonDb(serde::de::Error::custom(
+            "the database has no Pokémon",
+        )));
+    }
+    Ok(PokemonDb::new(pokemon))
+}
+
+/// Represents the Pokémon database, indexed by slug so looking a Pokémon up is O(1).
+///
+/// It dereferences to the slice of Pokémon, in database order, for iteration.
+pub struct PokemonDb {
+    pokemon: Vec<Pokemon>,
+    index: HashMap<String, usize>,
+}
+
+impl PokemonDb {
+    /// Indexes the given Pokémon by slug; with duplicate slugs the first one wins.
+    pub fn new(pokemon: Vec<Pokemon>) -> Self {
+        let mut index = HashMap::with_capacity(pokemon.len());
+        for (i, p) in pokemon.iter().enumerate() {
+            index.entry(p.slug.clone()).or_insert(i);
+        }
+        Self { pokemon, index }
+    }
+
+    /// Returns the Pokémon with the given slug, if any.
+    pub fn get(&self, slug: &str) -> Option<&Pokemon> {
+        self.index.get(slug).map(|&i| &self.pokemon[i])
+    }
+
+    /// Returns the Pokémon, dropping the index.
+    pub fn into_vec(self) -> Vec<Pokemon> {
+        self.pokemon
+    }
+}
+
+impl Deref for PokemonDb {
+    type Target = [Pokemon];
+
+    fn deref(&self) -> &[Pokemon] {
+        &self.pokemon
+    }
+}
+
+/// Decompresses the given bytes if they start with the gzip magic bytes.
+///
+/// # Parameters
+/// - `data`: The raw bytes, either gzip-compressed or plain.
+///
+/// # Returns
+/// - `Result<Cow<[u8]>, Error>`: The decompressed bytes, or the input untouched if it
+///   is not gzip-compressed.
+fn decompress(data: &[u8]) -> Result<Cow<'_, [u8]>, Error> {
+    if !data.starts_with(&GZIP_MAGIC) {
+        return Ok(Cow::Borrowed(data));
+    }
+
+    let mut decompressed = Vec::new();
+    GzDecoder::new(data).read_to_end(&mut decompressed)?;
+    Ok(Cow::Owned(decompressed))
+}
+
+/// Collapses the entries that are a form of another entry, as some datasets list forms
+/// as Pokémon of their own (e.g. `charizard-mega-x` next to `charizard`).
+///
+/// An entry is a form when dropping trailing `-` segments from its slug gives the slug of
+/// another entry that has the same National Dex number or lists the dropped part among
+/// its forms, or when the dropped part is a mega, Gigantamax or regional form. Hyphenated
+/// species such as `porygon-z` or `mr-mime` are kept.
+///
+/// # Parameters
+/// - `pokemon`: The entries to collapse.
+///
+/// # Returns
+/// - `(Vec<Pokemon>, usize)`: The entries left, in their original order, and how many were collapsed.
+pub fn species_only(pokemon: Vec<Pokemon>) -> (Vec<Pokemon>, usize) {
+    let by_slug: HashMap<&str, &Pokemon> = pokemon.iter().map(|p| (p.slug.as_str(), p)).collect();
+    let is_form = |p: &Pokemon| {
+        let mut base = p.slug.as_str();
+        while let Some((prefix, _)) = base.rsplit_once('-') {
+            base = prefix;
+            if let Some(species) = by_slug.get(base) {
+                let form = &p.slug[base.len() + 1..];
+                if p.idx > 0 && p.idx == species.idx
+                    || species.forms.iter().any(|f| f == form)
+                    || forms::is_mega(form)
+                    || forms::is_gmax(form)
+                    || forms::is_regional(form)
+                {
+                    return true;
+                }
+            }
+        }
+        false
+    };
+    let keep: Vec<bool> = pokemon.iter().map(|p| !is_form(p)).collect();
+    let collapsed = keep.iter().filter(|kept| !**kept).count();
+
+    let species = pokemon
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(p, kept)| kept.then_some(p))
+        .collect();
+    (species, collapsed)
 }
 
 /// Lists the slugs of all Pokémon in the provided database.
 ///
 /// # Parameters
+/// - `out`: The writer the list is written to.
 /// - `pokemon_db`: A vector of `Pokemon` objects from which to list the names.
 ///
-/// This function prints each Pokémon's slug to the standard output.
-pub fn list_pokemon_names(pokemon_db: Vec<Pokemon>) {
-    pokemon_db.iter().for_each(|p| println!("{}", p.slug));
+/// This function writes each Pokémon's slug on its own line.
+pub fn list_pokemon_names(out: &mut dyn Write, pokemon_db: &[Pokemon]) -> io::Result<()> {
+    pokemon_db
+        .iter()
+        .try_for_each(|p| writeln!(out, "{}", p.slug))
+}
+
+/// Prints how many Pokémon the provided database holds.
+///
+/// # Parameters
+/// - `out`: The writer the count is written to.
+/// - `pokemon_db`: A vector of `Pokemon` objects to count.
+/// - `by_gen`: Whether to print the count of every generation before the total.
+///
+/// Per-generation lines hold the generation (`unknown` for entries without one) and
+/// its count separated by a tab, followed by a `total` line.
+pub fn count_pokemon(out: &mut dyn Write, pokemon_db: &[Pokemon], by_gen: bool) -> io::Result<()> {
+    if !by_gen {
+        return writeln!(out, "{}", pokemon_db.len());
+    }
+    let mut counts: BTreeMap<u8, usize> = BTreeMap::new();
+    for p in pokemon_db.iter() {
+        *counts.entry(p.gen).or_default() += 1;
+    }
+    for (gen, count) in counts.iter().filter(|(gen, _)| **gen > 0) {
+        writeln!(out, "{gen}\t{count}")?;
+    }
+    if let Some(count) = counts.get(&0) {
+        writeln!(out, "unknown\t{count}")?;
+    }
+    writeln!(out, "total\t{}", pokemon_db.len())
+}
+
+/// Lists every Pokémon in the provided database with its generation and forms.
+///
+/// # Parameters
+/// - `out`: The writer the list is written to.
+/// - `pokemon_db`: A vector of `Pokemon` objects from which to list the details.
+///
+/// Each line holds the slug, the generation and a comma-separated list of the
+/// available forms, separated by tabs so the output can be fed to `cut` or `awk`.
+pub fn list_pokemon_details(out: &mut dyn Write, pokemon_db: &[Pokemon]) -> io::Result<()> {
+    for p in pokemon_db.iter() {
+        // The regular form is listed first, whether or not the data lists it
+        let regular = (!p.forms.iter().any(|form| form == "regular")).then_some("regular");
+        let forms = regular
+            .into_iter()
+            .chain(p.forms.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(",");
+        writeln!(out, "{}\t{}\t{}", p.slug, p.gen, forms)?;
+    }
+    Ok(())
+}
+
+/// Prints a summary of the Pokémon database, to tell which dataset a build uses.
+///
+/// # Parameters
+/// - `out`: The writer the summary is written to.
+/// - `pokemon_db`: The loaded Pokémon database.
+///
+/// The summary holds the number of Pokémon, the generations present, the languages of
+/// the names and descriptions, and how many Pokémon have stats and types.
+pub fn describe_dataset(out: &mut dyn Write, pokemon_db: &[Pokemon]) -> io::Result<()> {
+    let generations: BTreeSet<u8> = pokemon_db.iter().map(|p| p.gen).collect();
+    let name_languages: BTreeSet<&str> = pokemon_db
+        .iter()
+        .flat_map(|p| p.name.keys().map(String::as_str))
+        .collect();
+    let desc_languages: BTreeSet<&str> = pokemon_db
+        .iter()
+        .flat_map(|p| p.desc.keys().map(String::as_str))
+        .collect();
+    let with_stats = pokemon_db.iter().filter(|p| !p.stats.is_empty()).count();
+    let with_types = pokemon_db.iter().filter(|p| !p.types.is_empty()).count();
+    let join = |items: Vec<String>| items.join(", ");
+
+    writeln!(out, "Pokémon: {}", pokemon_db.len())?;
+    writeln!(
+        out,
+        "Generations: {}",
+        join(generations.iter().map(u8::to_string).collect())
+    )?;
+    writeln!(
+        out,
+        "Name languages: {}",
+        join(name_languages.iter().map(|l| l.to_string()).collect())
+    )?;
+    writeln!(
+        out,
+        "Description languages: {}",
+        join(desc_languages.iter().map(|l| l.to_string()).collect())
+    )?;
+    writeln!(out, "Stats: {}/{} Pokémon", with_stats, pokemon_db.len())?;
+    writeln!(out, "Types: {}/{} Pokémon", with_types, pokemon_db.len())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BULBASAUR: &str = r#"[{
+        "idx": 1,
+        "slug": "bulbasaur",
+        "gen": 1,
+        "name": {"en": "Bulbasaur", "fr": "Bulbizarre"},
+        "desc": {},
+        "forms": ["regular"]
+    }]"#;
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn load_pokemon_reads_plain_json() {
+        let pokemon_db = load_pokemon(BULBASAUR.as_bytes()).unwrap();
+
+        assert_eq!(pokemon_db.len(), 1);
+        assert_eq!(pokemon_db.get("bulbasaur").unwrap().idx, 1);
+    }
+
+    #[test]
+    fn load_pokemon_reads_gzip_json() {
+        let compressed = gzip(BULBASAUR.as_bytes());
+        assert!(compressed.starts_with(&GZIP_MAGIC));
+
+        let pokemon_db = load_pokemon(&compressed).unwrap();
+        assert_eq!(pokemon_db.len(), 1);
+        assert_eq!(pokemon_db[0].name["fr"], "Bulbizarre");
+    }
+
+    #[test]
+    fn decompress_leaves_plain_data_untouched() {
+        assert!(matches!(decompress(b"[]").unwrap(), Cow::Borrowed(b"[]")));
+        assert_eq!(&*decompress(&gzip(b"[]")).unwrap(), b"[]");
+    }
+
+    #[test]
+    fn load_pokemon_rejects_bad_data() {
+        assert!(matches!(load_pokemon(b"[]"), Err(Error::PokemonDb(_))));
+        assert!(matches!(load_pokemon(b"{}"), Err(Error::PokemonDb(_))));
+        // A truncated gzip stream fails to decompress
+        let compressed = gzip(BULBASAUR.as_bytes());
+        assert!(load_pokemon(&compressed[..compressed.len() / 2]).is_err());
+    }
+
+    #[test]
+    fn load_pokemon_reads_the_bundled_data() {
+        let pokemon_db = load_pokemon(include_bytes!("../assets/pokemon.json.gz")).unwrap();
+
+        assert!(pokemon_db.get("pikachu").is_some());
+    }
+
+    #[test]
+    fn list_pokemon_details_lists_regular_once() {
+        let data = r#"[
+            {"idx": 1, "slug": "bulbasaur", "gen": 1, "name": {}, "desc": {}, "forms": ["regular"]},
+            {"idx": 3, "slug": "venusaur", "gen": 1, "name": {}, "desc": {}, "forms": ["regular", "mega"]},
+            {"idx": 0, "slug": "custom", "gen": 0, "name": {}, "desc": {}, "forms": ["alt"]}
+        ]"#;
+        let pokemon_db = load_pokemon(data.as_bytes()).unwrap();
+        let mut out = Vec::new();
+        list_pokemon_details(&mut out, &pokemon_db).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "bulbasaur\t1\tregular\nvenusaur\t1\tregular,mega\ncustom\t0\tregular,alt\n"
+        );
+    }
+
+    #[test]
+    fn localized_name_tries_languages_in_order() {
+        let pokemon_db = load_pokemon(BULBASAUR.as_bytes()).unwrap();
+        let bulbasaur = pokemon_db.get("bulbasaur").unwrap();
+
+        assert_eq!(
+            bulbasaur.localized_name(&["fr", "en"]).unwrap(),
+            "Bulbizarre"
+        );
+        assert_eq!(
+            bulbasaur.localized_name(&["de", "en"]).unwrap(),
+            "Bulbasaur"
+        );
+    }
+
+    #[test]
+    fn localized_name_reports_the_missing_name() {
+        let pokemon_db = load_pokemon(BULBASAUR.as_bytes()).unwrap();
+        let error = pokemon_db[0].localized_name(&["de", "ja"]).unwrap_err();
+
+        assert!(matches!(
+            &error,
+            Error::MissingName(slug, languages) if slug == "bulbasaur" && languages == "de, ja"
+        ));
+        assert_eq!(error.exit_code(), 4);
+    }
 }
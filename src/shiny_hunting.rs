@@ -1,6 +1,10 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs::{self, OpenOptions};
 use std::io::{self, Write};
+use std::path::Path;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ShinyLogEntry {
@@ -8,6 +12,9 @@ pub struct ShinyLogEntry {
     pub form: String,
     pub date: String,
     pub details: String,
+    /// Number of encounters the shiny took, absent from older logs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encounters: Option<u32>,
 }
 
 /// Logs a shiny capture to the specified log file.
@@ -42,3 +49,153 @@ pub fn load_shiny_log(log_path: &str) -> io::Result<Vec<ShinyLogEntry>> {
     let entries: Vec<ShinyLogEntry> = serde_json::from_str(&data)?;
     Ok(entries)
 }
+
+/// Loads the encounter counters of the active hunts, keyed by Pokémon slug.
+///
+/// A missing file means no hunt is active.
+pub fn load_hunt_counters(path: &Path) -> io::Result<BTreeMap<String, u32>> {
+    match fs::read_to_string(path) {
+        Ok(data) => Ok(serde_json::from_str(&data)?),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Saves the encounter counters of the active hunts.
+pub fn save_hunt_counters(path: &Path, counters: &BTreeMap<String, u32>) -> io::Result<()> {
+    fs::write(path, serde_json::to_string_pretty(counters)?)
+}
+
+/// Rewrites the shiny log keeping only the entries that can still be read.
+///
+/// Parsing is best effort: every top-level object is read on its own, so a
+/// malformed entry or a truncated end only loses the entries involved. The
+/// original file is kept next to the log with a `.bak` extension.
+///
+/// Returns the number of entries kept and dropped.
+pub fn repair_shiny_log(log_path: &str) -> io::Result<(usize, usize)> {
+    let data = fs::read_to_string(log_path)?;
+    let mut entries: Vec<ShinyLogEntry> = Vec::new();
+    let mut dropped = 0;
+
+    let mut rest = data.trim_start();
+    rest = rest.strip_prefix('[').unwrap_or(rest);
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        if rest.is_empty() || rest.starts_with(']') {
+            break;
+        }
+
+        let mut stream = serde_json::Deserializer::from_str(rest).into_iter::<serde_json::Value>();
+        match stream.next() {
+            Some(Ok(value)) => {
+                rest = &rest[stream.byte_offset()..];
+                match serde_json::from_value(value) {
+                    Ok(entry) => entries.push(entry),
+                    Err(_) => dropped += 1,
+                }
+            }
+            _ => {
+                // Skip to the next object, or give up on a truncated end
+                dropped += 1;
+                let skip = rest.chars().next().map_or(1, char::len_utf8);
+                match rest[skip..].find('{') {
+                    Some(next) => rest = &rest[skip + next..],
+                    None => break,
+                }
+            }
+        }
+    }
+
+    fs::write(format!("{log_path}.bak"), &data)?;
+    fs::write(log_path, serde_json::to_string_pretty(&entries)?)?;
+    Ok((entries.len(), dropped))
+}
+
+/// Parses the date of a shiny log entry.
+///
+/// Accepts RFC 3339 timestamps, `YYYY-MM-DD HH:MM:SS` and plain `YYYY-MM-DD` dates.
+pub fn parse_entry_date(date: &str) -> Option<NaiveDate> {
+    let date = date.trim();
+    DateTime::parse_from_rfc3339(date)
+        .map(|d| d.date_naive())
+        .or_else(|_| NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M:%S").map(|d| d.date()))
+        .or_else(|_| NaiveDate::parse_from_str(date, "%Y-%m-%d"))
+        .ok()
+}
+
+/// Represents the shiny hunting methods with a known base rate, as in the recent games.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum HuntMethod {
+    FullOdds,
+    ShinyCharm,
+    Masuda,
+    MasudaCharm,
+}
+
+impl HuntMethod {
+    /// Returns the probability of a single encounter being shiny with this method.
+    pub fn rate(self) -> f64 {
+        let rolls = match self {
+            HuntMethod::FullOdds => 1.0,
+            HuntMethod::ShinyCharm => 3.0,
+            HuntMethod::Masuda => 6.0,
+            HuntMethod::MasudaCharm => 8.0,
+        };
+        rolls / 4096.0
+    }
+}
+
+/// Returns the probability of having found at least one shiny after a number of
+/// encounters, each shiny with probability `rate`.
+pub fn shiny_probability(rate: f64, encounters: u64) -> f64 {
+    1.0 - (1.0 - rate).powf(encounters as f64)
+}
+
+/// Returns the number of encounters after which at least one shiny has been found
+/// with the given probability, or `None` if it is never reached (a rate of 0).
+pub fn encounters_for(rate: f64, probability: f64) -> Option<u64> {
+    if rate <= 0.0 {
+        return None;
+    }
+    if rate >= 1.0 {
+        return Some(1);
+    }
+    Some(((1.0 - probability).ln() / (1.0 - rate).ln()).ceil() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_log(name: &str, contents: &str) -> String {
+        let path = std::env::temp_dir().join(format!("kingler-{}-{name}.json", std::process::id()));
+        fs::write(&path, contents).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn repair_keeps_valid_entries_around_garbage() {
+        let entry =
+            r#"{"pokemon_name":"pikachu","form":"regular","date":"2024-01-01","details":""}"#;
+        let path = temp_log("repair", &format!("[{entry}, oops{{\"x\":1}}, {entry}"));
+
+        assert_eq!(repair_shiny_log(&path).unwrap(), (2, 2));
+        assert_eq!(load_shiny_log(&path).unwrap().len(), 2);
+
+        fs::remove_file(format!("{path}.bak")).unwrap();
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn repair_skips_non_ascii_garbage() {
+        let entry =
+            r#"{"pokemon_name":"pikachu","form":"regular","date":"2024-01-01","details":""}"#;
+        let path = temp_log("repair-utf8", &format!("[{entry}, é{{\"x\":1}}]"));
+
+        assert_eq!(repair_shiny_log(&path).unwrap(), (1, 2));
+
+        fs::remove_file(format!("{path}.bak")).unwrap();
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -1,35 +1,509 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+
+use crate::ascii::{color_enabled, visible_width};
+use crate::config::StatTiers;
+use crate::error::Error;
+use crate::language::Language;
 use crate::Pokemon;
 
+/// The stat keys as found in the Pokémon database, followed by the total.
+const STAT_KEYS: [&str; 7] = [
+    "hp",
+    "attack",
+    "defense",
+    "special-attack",
+    "special-defense",
+    "speed",
+    "total",
+];
+
+/// Returns the stat labels for a language, in `STAT_KEYS` order.
+///
+/// # Parameters
+/// - `language`: The language of the labels.
+///
+/// # Returns
+/// - `Option<[&str; 7]>`: The translated labels, or `None` if the language has no translation.
+fn stat_labels(language: Language) -> Option<[&'static str; 7]> {
+    match language {
+        Language::Fr => Some([
+            "PV",
+            "Attaque",
+            "Défense",
+            "Attaque Spé.",
+            "Défense Spé.",
+            "Vitesse",
+            "Total",
+        ]),
+        Language::De => Some([
+            "KP",
+            "Angriff",
+            "Verteidigung",
+            "Sp.-Angriff",
+            "Sp.-Verteidigung",
+            "Initiative",
+            "Gesamt",
+        ]),
+        Language::It => Some([
+            "PS",
+            "Attacco",
+            "Difesa",
+            "Attacco Sp.",
+            "Difesa Sp.",
+            "Velocità",
+            "Totale",
+        ]),
+        Language::Es => Some([
+            "PS",
+            "Ataque",
+            "Defensa",
+            "Ataque Esp.",
+            "Defensa Esp.",
+            "Velocidad",
+            "Total",
+        ]),
+        Language::Ja | Language::JaHrkt => Some([
+            "HP",
+            "こうげき",
+            "ぼうぎょ",
+            "とくこう",
+            "とくぼう",
+            "すばやさ",
+            "ごうけい",
+        ]),
+        Language::Ko => Some([
+            "HP",
+            "공격",
+            "방어",
+            "특수공격",
+            "특수방어",
+            "스피드",
+            "합계",
+        ]),
+        Language::ZhHans => Some(["HP", "攻击", "防御", "特攻", "特防", "速度", "总计"]),
+        Language::ZhHant => Some(["HP", "攻擊", "防禦", "特攻", "特防", "速度", "總計"]),
+        Language::En => None,
+    }
+}
+
+/// Returns the label of a stat in the given language, falling back to the stat key.
+fn stat_label<'a>(key: &'a str, language: &str) -> &'a str {
+    let index = STAT_KEYS.iter().position(|k| *k == key);
+    let labels = Language::from_code(language).ok().and_then(stat_labels);
+    match (labels, index) {
+        (Some(labels), Some(i)) => labels[i],
+        _ => key,
+    }
+}
+
+/// Pads a label with a trailing colon to the given number of terminal columns.
+fn pad_label(label: &str, width: usize) -> String {
+    let label = format!("{}:", label);
+    let padding = width.saturating_sub(visible_width(&label));
+    format!("{}{}", label, " ".repeat(padding))
+}
+
 /// Displays the stats of a given Pokémon.
 ///
-/// This function checks if the Pokémon has stats available. If stats are present,
+/// This function checks if the form has stats available, falling back to the regular
+/// form. If stats are present,
 /// it prints the HP, Attack, Defense, Special Attack, Special Defense, and Speed
-/// in a formatted manner. If stats are not available, a message is printed
+/// in a formatted manner, followed by their total. The labels are translated to
+/// the configured language when a translation exists, and are the English stat
+/// keys otherwise. If stats are not available, a message is printed
 /// indicating that stats are not available for the Pokémon.
 ///
 /// # Parameters
+/// - `out`: The writer the stats are written to.
 /// - `pokemon`: A reference to a `Pokemon` struct containing the stats to be displayed.
-pub fn display_pokemon_stats(pokemon: &Pokemon) {
-    if let Some(stats) = &pokemon.stats {
-        let stat_pairs = [
-            ("hp", "speed"),
-            ("attack", "special-attack"),
-            ("defense", "special-defense"),
-        ];
+/// - `form`: The form whose stats are displayed, `regular` for the base form.
+/// - `language`: The language code used for the stat labels.
+/// - `tiers`: The thresholds the values are colored by, red for low, yellow for mid
+///   and green for high, when colors are enabled.
+pub fn display_pokemon_stats(
+    out: &mut dyn Write,
+    pokemon: &Pokemon,
+    form: &str,
+    language: &str,
+    tiers: &StatTiers,
+) -> io::Result<()> {
+    match pokemon.stats_for(form) {
+        Some(stats) => {
+            let total = STAT_KEYS.iter().filter_map(|k| stats.get(*k)).sum();
+            write_stats(out, stats, total, language, tiers)
+        }
+        None => writeln!(out, "\nStats not available for this Pokémon."),
+    }
+}
+
+/// Writes the six stats in pairs, followed by the total, each value colored by its tier.
+///
+/// # Parameters
+/// - `out`: The writer the stats are written to.
+/// - `stats`: The stat values keyed by stat; missing stats are shown as 0.
+/// - `total`: The total shown on the last line.
+/// - `language`: The language code used for the stat labels.
+/// - `tiers`: The thresholds the values are colored by when colors are enabled.
+fn write_stats(
+    out: &mut dyn Write,
+    stats: &HashMap<String, u32>,
+    total: u32,
+    language: &str,
+    tiers: &StatTiers,
+) -> io::Result<()> {
+    let color = color_enabled();
+    let stat_pairs = [
+        ("hp", "speed"),
+        ("attack", "special-attack"),
+        ("defense", "special-defense"),
+    ];
+
+    for &(stat1, stat2) in &stat_pairs {
+        let value1 = stats.get(stat1).unwrap_or(&0);
+        let value2 = stats.get(stat2).unwrap_or(&0);
 
-        for &(stat1, stat2) in &stat_pairs {
-            let value1 = stats.get(stat1).unwrap_or(&0);
-            let value2 = stats.get(stat2).unwrap_or(&0);
-
-            println!(
-                "{:<15} {:<5}  {:<15} {}",
-                format!("{}:", stat1),
-                value1,
-                format!("{}:", stat2),
-                value2
-            );
+        writeln!(
+            out,
+            "{} {}  {} {}",
+            pad_label(stat_label(stat1, language), 15),
+            tier_value(
+                *value1,
+                format!("{value1:<5}"),
+                tiers.low,
+                tiers.high,
+                color
+            ),
+            pad_label(stat_label(stat2, language), 15),
+            tier_value(*value2, value2.to_string(), tiers.low, tiers.high, color)
+        )?;
+    }
+
+    writeln!(
+        out,
+        "{} {}",
+        pad_label(stat_label("total", language), 15),
+        tier_value(
+            total,
+            total.to_string(),
+            tiers.low * 6,
+            tiers.high * 6,
+            color
+        )
+    )
+}
+
+/// Displays the stats of a given Pokémon as a hexagonal radar chart.
+///
+/// HP is at the top, then Attack, Defense, Speed, Special Defense and Special Attack
+/// clockwise, as in the games. The outer hexagon stands for 150, or for the highest
+/// stat when it is above that, and each stat is plotted along its axis in proportion.
+/// The labels and the total are translated and colored like with [`display_pokemon_stats`].
+///
+/// # Parameters
+/// - `out`: The writer the chart is written to.
+/// - `pokemon`: A reference to a `Pokemon` struct containing the stats to be displayed.
+/// - `form`: The form whose stats are displayed, `regular` for the base form.
+/// - `language`: The language code used for the stat labels.
+/// - `tiers`: The thresholds the values are colored by when colors are enabled.
+pub fn display_pokemon_radar(
+    out: &mut dyn Write,
+    pokemon: &Pokemon,
+    form: &str,
+    language: &str,
+    tiers: &StatTiers,
+) -> io::Result<()> {
+    // Half the height, in rows; columns are twice as many as terminal cells are about twice as tall
+    const RADIUS: f64 = 6.0;
+    // Clockwise from the top
+    const AXES: [&str; 6] = [
+        "hp",
+        "attack",
+        "defense",
+        "speed",
+        "special-defense",
+        "special-attack",
+    ];
+
+    let Some(stats) = pokemon.stats_for(form) else {
+        return writeln!(out, "\nStats not available for this Pokémon.");
+    };
+    let color = color_enabled();
+    let values = AXES.map(|key| *stats.get(key).unwrap_or(&0));
+    let scale = f64::from(values.iter().copied().max().unwrap_or(0).max(150));
+
+    let (width, height) = (4 * RADIUS as usize + 1, 2 * RADIUS as usize + 1);
+    let center = (2.0 * RADIUS, RADIUS);
+    // The point at `ratio` of the way along an axis, as (column, row)
+    let point = |axis: usize, ratio: f64| {
+        let angle = std::f64::consts::FRAC_PI_2 - std::f64::consts::FRAC_PI_3 * axis as f64;
+        (
+            center.0 + ratio * 2.0 * RADIUS * angle.cos(),
+            center.1 - ratio * RADIUS * angle.sin(),
+        )
+    };
+
+    let mut grid = vec![vec![' '; width]; height];
+    let mut draw_line = |from: (f64, f64), to: (f64, f64), c: char| {
+        let steps = ((to.0 - from.0).abs().max((to.1 - from.1).abs()) * 2.0).ceil() as usize;
+        for step in 0..=steps {
+            let t = step as f64 / steps.max(1) as f64;
+            let column = (from.0 + (to.0 - from.0) * t).round() as usize;
+            let row = (from.1 + (to.1 - from.1) * t).round() as usize;
+            grid[row.min(height - 1)][column.min(width - 1)] = c;
         }
+    };
+    for axis in 0..AXES.len() {
+        draw_line(point(axis, 1.0), point((axis + 1) % AXES.len(), 1.0), '.');
+    }
+    for axis in 0..AXES.len() {
+        let next = (axis + 1) % AXES.len();
+        draw_line(
+            point(axis, f64::from(values[axis]) / scale),
+            point(next, f64::from(values[next]) / scale),
+            '#',
+        );
+    }
+
+    let label = |axis: usize| {
+        format!(
+            "{} {}",
+            stat_label(AXES[axis], language),
+            tier_value(
+                values[axis],
+                values[axis].to_string(),
+                tiers.low,
+                tiers.high,
+                color
+            )
+        )
+    };
+    // The side vertices are half a radius above and below the center
+    let upper_row = (RADIUS / 2.0) as usize;
+    let lower_row = height - 1 - upper_row;
+    let left_width = [label(4), label(5)]
+        .iter()
+        .map(|l| visible_width(l))
+        .max()
+        .unwrap_or(0);
+    let indent = left_width + 1;
+
+    let centered = |text: String| {
+        let padding = (indent + width / 2).saturating_sub(visible_width(&text) / 2);
+        format!("{}{}", " ".repeat(padding), text)
+    };
+    writeln!(out, "{}", centered(label(0)))?;
+    for (row, cells) in grid.iter().enumerate() {
+        let (left, right) = match row {
+            _ if row == upper_row => (label(5), label(1)),
+            _ if row == lower_row => (label(4), label(2)),
+            _ => (String::new(), String::new()),
+        };
+        let padding = left_width.saturating_sub(visible_width(&left));
+        let line = format!(
+            "{}{} {} {}",
+            " ".repeat(padding),
+            left,
+            cells.iter().collect::<String>(),
+            right
+        );
+        writeln!(out, "{}", line.trim_end())?;
+    }
+    writeln!(out, "{}", centered(label(3)))?;
+
+    let total: u32 = values.iter().sum();
+    writeln!(
+        out,
+        "{} {}",
+        pad_label(stat_label("total", language), 15),
+        tier_value(
+            total,
+            total.to_string(),
+            tiers.low * 6,
+            tiers.high * 6,
+            color
+        )
+    )
+}
+
+/// Wraps a formatted stat value in red, yellow or green depending on its tier.
+///
+/// # Parameters
+/// - `value`: The stat value, compared with the thresholds.
+/// - `text`: The value as it should be printed, padding included.
+/// - `low`: Values under this are low (red).
+/// - `high`: Values above this are high (green), the others are mid (yellow).
+/// - `color`: Whether to color the text at all.
+fn tier_value(value: u32, text: String, low: u32, high: u32, color: bool) -> String {
+    if !color {
+        return text;
+    }
+
+    let code = if value < low {
+        31
+    } else if value > high {
+        32
     } else {
-        println!("\nStats not available for this Pokémon.");
+        33
+    };
+    format!("\x1b[{code}m{text}\x1b[0m")
+}
+
+/// Returns the value of a stat for the regular form of a Pokémon, `total` being the sum of all six.
+fn stat_value(pokemon: &Pokemon, stat: &str) -> Option<u32> {
+    let stats = pokemon.stats_for("regular")?;
+    match stat {
+        "total" => Some(STAT_KEYS.iter().filter_map(|k| stats.get(*k)).sum()),
+        stat => stats.get(stat).copied(),
+    }
+}
+
+/// Returns the Pokémon of the given generations, and of the given type if any.
+///
+/// Filtering by type fails with `Error::MissingData` when the dataset has no types at all,
+/// rather than leaving no Pokémon.
+fn filter_candidates<'a>(
+    pokemon_db: &'a [Pokemon],
+    generations: &[RangeInclusive<u8>],
+    type_name: Option<&str>,
+) -> Result<Vec<&'a Pokemon>, Error> {
+    if type_name.is_some() && pokemon_db.iter().all(|p| p.types.is_empty()) {
+        return Err(Error::MissingData("types".into()));
+    }
+
+    Ok(pokemon_db
+        .iter()
+        .filter(|p| generations.iter().any(|range| range.contains(&p.gen)))
+        .filter(|p| type_name.is_none_or(|t| p.types.iter().any(|pt| pt.eq_ignore_ascii_case(t))))
+        .collect())
+}
+
+/// Prints the Pokémon with the highest value of a stat.
+///
+/// Pokémon without the stat are left out of the ranking, and their count is
+/// printed after it.
+///
+/// # Parameters
+/// - `pokemon_db`: The Pokémon to rank.
+/// - `stat`: The stat to sort by, one of the six stat keys or `total`.
+/// - `generations`: The generations the Pokémon must belong to.
+/// - `type_name`: An optional type the Pokémon must have.
+/// - `top`: The number of Pokémon to print.
+///
+/// # Returns
+/// - `Result<(), Error>`: Returns `Ok(())` on success, `Error::InvalidStat` for an unknown stat,
+///   or `Error::MissingData` when filtering by type a dataset without types.
+pub fn display_stat_ranking(
+    pokemon_db: &[Pokemon],
+    stat: &str,
+    generations: &[RangeInclusive<u8>],
+    type_name: Option<&str>,
+    top: usize,
+) -> Result<(), Error> {
+    if !STAT_KEYS.contains(&stat) {
+        return Err(Error::InvalidStat(stat.to_string()));
+    }
+
+    let candidates = filter_candidates(pokemon_db, generations, type_name)?;
+    let mut ranking: Vec<(&Pokemon, u32)> = candidates
+        .iter()
+        .filter_map(|p| stat_value(p, stat).map(|value| (*p, value)))
+        .collect();
+    let excluded = candidates.len() - ranking.len();
+
+    // Highest first, ties in Pokédex order
+    ranking.sort_by_key(|(_, value)| Reverse(*value));
+
+    for (i, (pokemon, value)) in ranking.iter().take(top).enumerate() {
+        println!("{:>3}. {:<25} {}", i + 1, pokemon.slug, value);
+    }
+    if excluded > 0 {
+        println!("\n{} Pokémon without {} excluded.", excluded, stat);
+    }
+
+    Ok(())
+}
+
+/// Prints the average base stats of a group of Pokémon, laid out like [`display_pokemon_stats`].
+///
+/// The averages are taken over the regular form of the Pokémon having stats, and are
+/// rounded to the nearest integer. The sample size is printed first, along with the
+/// number of Pokémon left out for lacking stats.
+///
+/// # Parameters
+/// - `pokemon_db`: The Pokémon to average.
+/// - `generations`: The generations the Pokémon must belong to.
+/// - `type_name`: An optional type the Pokémon must have.
+/// - `language`: The language code used for the stat labels.
+/// - `tiers`: The thresholds the averages are colored by when colors are enabled.
+///
+/// # Returns
+/// - `Result<(), Error>`: Returns `Ok(())` on success, or an `Error` if writing fails.
+pub fn display_stat_averages(
+    pokemon_db: &[Pokemon],
+    generations: &[RangeInclusive<u8>],
+    type_name: Option<&str>,
+    language: &str,
+    tiers: &StatTiers,
+) -> Result<(), Error> {
+    let candidates = filter_candidates(pokemon_db, generations, type_name)?;
+    let samples: Vec<&HashMap<String, u32>> = candidates
+        .iter()
+        .filter_map(|p| p.stats_for("regular"))
+        .collect();
+    let excluded = candidates.len() - samples.len();
+
+    let mut out = io::stdout().lock();
+    write!(out, "Average of {} Pokémon", samples.len())?;
+    if excluded > 0 {
+        write!(out, " ({} without stats excluded)", excluded)?;
+    }
+    writeln!(out)?;
+    if samples.is_empty() {
+        return Ok(());
+    }
+
+    let count = samples.len() as f64;
+    let average = |sum: u32| (f64::from(sum) / count).round() as u32;
+    let averages: HashMap<String, u32> = STAT_KEYS[..6]
+        .iter()
+        .map(|k| {
+            let sum = samples.iter().filter_map(|stats| stats.get(*k)).sum();
+            (k.to_string(), average(sum))
+        })
+        .collect();
+    let total = samples
+        .iter()
+        .map(|stats| STAT_KEYS.iter().filter_map(|k| stats.get(*k)).sum::<u32>())
+        .sum();
+
+    write_stats(&mut out, &averages, average(total), language, tiers)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pad_label_aligns_double_width_labels() {
+        let labels = [
+            stat_label("attack", "en"),
+            stat_label("attack", "ja"),
+            stat_label("attack", "zh-Hant"),
+        ];
+        assert_eq!(labels, ["attack", "こうげき", "攻擊"]);
+
+        for label in labels {
+            assert_eq!(visible_width(&pad_label(label, 15)), 15);
+        }
+        assert_eq!(pad_label("こうげき", 15), "こうげき:      ");
+    }
+
+    #[test]
+    fn pad_label_keeps_labels_wider_than_the_column() {
+        assert_eq!(pad_label("とくぼう", 4), "とくぼう:");
     }
 }
@@ -0,0 +1,59 @@
+/// The standard type palette, as RGB triples keyed by the lowercase type name.
+const TYPE_COLORS: [(&str, (u8, u8, u8)); 18] = [
+    ("normal", (168, 167, 122)),
+    ("fire", (238, 129, 48)),
+    ("water", (99, 144, 240)),
+    ("electric", (247, 208, 44)),
+    ("grass", (122, 199, 76)),
+    ("ice", (150, 217, 214)),
+    ("fighting", (194, 46, 40)),
+    ("poison", (163, 62, 161)),
+    ("ground", (226, 191, 101)),
+    ("flying", (169, 143, 243)),
+    ("psychic", (249, 85, 135)),
+    ("bug", (166, 185, 26)),
+    ("rock", (182, 161, 54)),
+    ("ghost", (115, 87, 151)),
+    ("dragon", (111, 53, 252)),
+    ("dark", (112, 87, 70)),
+    ("steel", (183, 183, 206)),
+    ("fairy", (214, 133, 173)),
+];
+
+/// Formats the types of a Pokémon as a chip, e.g. `[Grass/Poison]`.
+///
+/// Every type name is capitalized and, if `color` is set, colored with its
+/// entry in the standard type palette. Unknown types are left uncolored.
+///
+/// # Parameters
+/// - `types`: The lowercase type names of the Pokémon.
+/// - `color`: Whether to wrap the type names in ANSI color codes.
+///
+/// # Returns
+/// - `Option<String>`: The formatted chip, or `None` if there is no type data.
+pub fn format_types(types: &[String], color: bool) -> Option<String> {
+    if types.is_empty() {
+        return None;
+    }
+
+    let names: Vec<String> = types
+        .iter()
+        .map(|t| {
+            let mut chars = t.chars();
+            let name = match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            };
+
+            match TYPE_COLORS
+                .iter()
+                .find(|(slug, _)| *slug == t.to_lowercase())
+            {
+                Some((_, (r, g, b))) if color => format!("\x1b[38;2;{r};{g};{b}m{name}\x1b[0m"),
+                _ => name,
+            }
+        })
+        .collect();
+
+    Some(format!("[{}]", names.join("/")))
+}
@@ -1,4 +1,5 @@
 import requests
+import gzip
 import json
 
 
@@ -54,7 +55,9 @@ def fetch_basic_pokemon_data(pokemon_id):
             "slug": data['name'],  # Get the Pokémon's name (slug)
             "idx": data['id'],  # Get the Pokémon's ID
             "stats": {stat['stat']['name']: stat['base_stat']
-                      for stat in data['stats']}  # Fetch stats data
+                      for stat in data['stats']},  # Fetch stats data
+            "types": [t['type']['name']
+                      for t in sorted(data['types'], key=lambda t: t['slot'])]
         }
     else:
         print(f"Failed to fetch data for Pokémon ID {pokemon_id}: "
@@ -64,13 +67,14 @@ def fetch_basic_pokemon_data(pokemon_id):
 
 def fetch_species_data(pokemon_id):
     """
-    Fetch species data (flavor text) for a given Pokémon ID.
+    Fetch species data (flavor text and category) for a given Pokémon ID.
 
     Args:
         pokemon_id (int): The ID of the Pokémon.
 
     Returns:
-        dict: A dictionary containing the flavor text for different languages.
+        tuple: A dictionary containing the flavor text for different languages,
+            and the category (normal, legendary or mythical).
     """
     species_url = "https://pokeapi.co/api/v2/pokemon-species/"
     response = requests.get(f"{species_url}{pokemon_id}")
@@ -92,20 +96,29 @@ def fetch_species_data(pokemon_id):
             # Store the formatted flavor text based on the version
             language_descriptions[language][version] = formatted_flavor_text
 
-        return language_descriptions  # Return the language descriptions
+        if species_data['is_mythical']:
+            category = "mythical"
+        elif species_data['is_legendary']:
+            category = "legendary"
+        else:
+            category = "normal"
+
+        return language_descriptions, category  # Return the descriptions and category
     else:
         print(f"Failed to fetch species data for Pokémon ID {pokemon_id}: "
               f"{response.status_code}")
         return None  # Return None if the fetch fails
 
 
-def format_pokemon_info(pokemon_data, language_descriptions):
+def format_pokemon_info(pokemon_data, language_descriptions, category):
     """
     Create a structured dictionary with relevant Pokémon information.
 
     Args:
-        pokemon_data (dict): Basic Pokémon data including 'slug', 'idx', and 'stats'.
+        pokemon_data (dict): Basic Pokémon data including 'slug', 'idx', 'stats'
+            and 'types'.
         language_descriptions (dict): Flavor text descriptions by language.
+        category (str): The category of the Pokémon (normal, legendary or mythical).
 
     Returns:
         dict: A structured dictionary containing formatted Pokémon info.
@@ -123,7 +136,9 @@ def format_pokemon_info(pokemon_data, language_descriptions):
             "en": slug.capitalize(),  # Capitalize slug for the English name
         },
         "desc": language_descriptions,  # Use descriptions by language
-        "stats": stats,  # Add the stats data
+        "stats": {"regular": stats},  # Add the stats data, keyed by form
+        "types": pokemon_data['types'],  # Add the types in slot order
+        "category": category,  # Add the category
         "forms": [],  # Placeholder for forms
     }
     return pokemon_info  # Return the Pokémon info
@@ -145,7 +160,8 @@ def fetch_pokemon_data():
             species_data = fetch_species_data(pokemon_id)  # Fetch species data
             if species_data:
                 # Format and append Pokémon info to the list
-                formatted_info = format_pokemon_info(basic_data, species_data)
+                language_descriptions, category = species_data
+                formatted_info = format_pokemon_info(basic_data, language_descriptions, category)
                 pokemon_data_list.append(formatted_info)
 
     return pokemon_data_list  # Return the list of Pokémon data
@@ -157,10 +173,10 @@ def main():
     """
     all_pokemon = fetch_pokemon_data()  # Fetch all Pokémon data
 
-    # Save the Pokémon data to a JSON file
-    with open('pokemon.json', 'w') as json_file:
+    # Save the Pokémon data to a gzip-compressed JSON file
+    with gzip.open('pokemon.json.gz', 'wt', encoding='utf-8') as json_file:
         json.dump(all_pokemon, json_file, indent=4)  # Write data to file
-    print("Pokédex data saved to pokemon.json")
+    print("Pokédex data saved to pokemon.json.gz")
 
 
 if __name__ == "__main__":